@@ -11,15 +11,78 @@
 //! ## Feature flags
 //!
 //! - `all`
-//! - `tokio-async` - for enable async features
+//! - `async` - for enable async features on any executor
+//! - `tokio-async` - for enable async features with tokio
 //! - `google` - for enable google-translate
+//! - `libre` - for enable LibreTranslate, self-hosted or public instances
+//! - `deepl` - for enable the DeepL API, free and pro
+//! - `bing` - for enable Bing Translator, keyless web or Azure API
+//! - `yandex` - for enable Yandex Translate, without key
+//! - `mymemory` - for enable MyMemory, free with a daily quota
+//! - `lingva` - for enable Lingva, self-hosted or public instances
+//! - `llm` - for enable the language models behind an OpenAI-compatible API
+//! - `fallback` - for enable the chain of translators tried in order
+//! - `offline-detect` - for enable offline language detection
+//! - `reqwest` (default) - for enable the reqwest HTTP transport, needed by `tokio-async`
+//! - `native-roots` - for enable the certificates of the OS store in `TlsConfig`
+//! - `ureq` - for enable the small ureq transport, the default one with
+//!   `default-features = false` for a sync-only build without tokio
 //!
 
 mod translators;
 
 // general
+#[cfg(feature = "bing")]
+pub use translators::bing::session::BingSession;
+#[cfg(feature = "bing")]
+pub use translators::bing::translator::BingTranslator;
+pub use translators::circuit::{CircuitBreaker, CircuitPermit, CircuitState};
+pub use translators::concurrency::{AdaptiveConcurrency, ConcurrencyPermit};
+#[cfg(feature = "deepl")]
+pub use translators::deepl::translator::{DeepLTranslator, DeepLUsage, Formality, TagHandling};
+pub use translators::detector::Detection;
+pub use translators::detector::LanguageDetector;
+#[cfg(feature = "fallback")]
+pub use translators::fallback::{
+    FallbackBackend, FallbackTranslation, FallbackTranslator, ServedChunk,
+};
+#[cfg(feature = "async")]
+pub use translators::hedging::Hedging;
+pub use translators::registry::BackendConfig;
+pub use translators::registry::TranslatorFactory;
+pub use translators::registry::TranslatorRegistry;
+pub use translators::rotation::RotationPool;
+#[cfg(feature = "async")]
+pub use translators::translator::BoxFuture;
+pub use translators::translator::DynTranslator;
 pub use translators::translator::Error;
+pub use translators::translator::ErrorKind;
+pub use translators::translator::Language;
 pub use translators::translator::Translator;
+pub use translators::transport::CookieJar;
+pub use translators::transport::IpVersion;
+pub use translators::transport::Method;
+pub use translators::transport::Request;
+#[cfg(feature = "reqwest")]
+pub use translators::transport::ReqwestTransport;
+pub use translators::transport::Response;
+pub use translators::transport::TlsConfig;
+pub use translators::transport::Transport;
+#[cfg(feature = "ureq")]
+pub use translators::transport::UreqTransport;
+
+#[cfg(feature = "offline-detect")]
+pub use translators::detector::offline::OfflineDetector;
 
 #[cfg(feature = "google")]
 pub use translators::google::translator::GoogleTranslator;
+#[cfg(feature = "libre")]
+pub use translators::libre::translator::LibreTranslator;
+#[cfg(feature = "lingva")]
+pub use translators::lingva::translator::LingvaTranslator;
+#[cfg(feature = "llm")]
+pub use translators::llm::translator::LlmTranslator;
+#[cfg(feature = "mymemory")]
+pub use translators::mymemory::translator::MyMemoryTranslator;
+#[cfg(feature = "yandex")]
+pub use translators::yandex::translator::YandexTranslator;
@@ -0,0 +1,4 @@
+pub(crate) mod languages;
+mod requests;
+pub(crate) mod session;
+pub(crate) mod translator;
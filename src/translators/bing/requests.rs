@@ -0,0 +1,472 @@
+use crate::translators::bing::session::{BingSession, WebToken};
+use crate::translators::translator::shared::{self, Connection};
+use crate::translators::translator::{self, ErrorKind};
+use crate::translators::transport::{Request, Response};
+use regex::Regex;
+use serde_json::{json, Value};
+use std::time::{Duration, Instant};
+use urlencoding::encode;
+
+/// Settings of a single request.
+#[derive(Debug, Clone, Copy)]
+pub struct RequestConfig<'a> {
+    pub connection: Connection<'a>,
+    /// Url of the web translator or of the Azure API, without the endpoint.
+    pub url: &'a str,
+    /// Key of the Azure API, the web translator is used without it.
+    pub api_key: Option<&'a str>,
+    pub region: Option<&'a str>,
+    pub user_agent: Option<&'a str>,
+    pub session: &'a BingSession,
+}
+
+impl RequestConfig<'_> {
+    fn request(&self, request: Request) -> Request {
+        let mut request = self.connection.request(request);
+        if let Some(user_agent) = self.user_agent {
+            request = request.header("User-Agent", user_agent);
+        }
+
+        request
+    }
+
+    fn endpoint_url(&self, endpoint: &str) -> String {
+        format!("{}/{}", self.url.trim_end_matches('/'), endpoint)
+    }
+
+    /// Page of the web translator, with the token.
+    fn web_page(&self) -> Request {
+        self.with_cookies(self.request(Request::get(self.endpoint_url("translator"))))
+    }
+
+    /// Form posted to the web translator.
+    fn web_post(&self, endpoint: &str, token: &WebToken, form: &[(&str, &str)]) -> Request {
+        let url = self.endpoint_url(&format!(
+            "{}?isVertical=1&IG={}&IID={}",
+            endpoint,
+            encode(&token.ig),
+            encode(&token.iid)
+        ));
+        let body = form
+            .iter()
+            .chain([&("token", &*token.token), &("key", &*token.key)])
+            .map(|(name, value)| format!("{}={}", name, encode(value)))
+            .collect::<Vec<_>>()
+            .join("&");
+        self.with_cookies(self.request(Request::post(url, body)))
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .header("Referer", self.endpoint_url("translator"))
+    }
+
+    fn with_cookies(&self, request: Request) -> Request {
+        match self.session.cookie_jar().header(&request.url) {
+            Some(cookie) => request.header("Cookie", cookie),
+            None => request,
+        }
+    }
+
+    fn store_cookies(&self, response: &Response) {
+        for set_cookie in response.header_all("set-cookie") {
+            self.session.cookie_jar().store(&response.url, set_cookie);
+        }
+    }
+
+    /// `POST` of the texts to the Azure API.
+    fn api_post(&self, endpoint: &str, texts: &[&str]) -> Request {
+        let body: Vec<Value> = texts.iter().map(|text| json!({ "Text": text })).collect();
+        let mut request = self
+            .request(Request::post(
+                self.endpoint_url(endpoint),
+                Value::from(body).to_string(),
+            ))
+            .header("Content-Type", "application/json")
+            .header(
+                "Ocp-Apim-Subscription-Key",
+                self.api_key.unwrap_or_default(),
+            );
+        if let Some(region) = self.region {
+            request = request.header("Ocp-Apim-Subscription-Region", region);
+        }
+
+        request
+    }
+}
+
+#[cfg(feature = "async")]
+pub async fn send_async_web_request(
+    target_language: &str,
+    source_language: &str,
+    text: &str,
+    config: &RequestConfig<'_>,
+) -> Result<String, translator::Error> {
+    let form = translate_form(target_language, source_language, text);
+    let result = web_post_async(config, "ttranslatev3", &form).await?;
+
+    get_translated_texts(&result)?
+        .pop()
+        .ok_or_else(|| translator::Error::new(ErrorKind::DecodeBody, result.to_string()))
+}
+
+pub fn send_sync_web_request(
+    target_language: &str,
+    source_language: &str,
+    text: &str,
+    config: &RequestConfig<'_>,
+) -> Result<String, translator::Error> {
+    let form = translate_form(target_language, source_language, text);
+    let result = web_post_sync(config, "ttranslatev3", &form)?;
+
+    get_translated_texts(&result)?
+        .pop()
+        .ok_or_else(|| translator::Error::new(ErrorKind::DecodeBody, result.to_string()))
+}
+
+#[cfg(feature = "async")]
+pub async fn send_async_web_transliterate_request(
+    language: &str,
+    from_script: &str,
+    to_script: &str,
+    text: &str,
+    config: &RequestConfig<'_>,
+) -> Result<String, translator::Error> {
+    let form = transliterate_form(language, from_script, to_script, text);
+    let result = web_post_async(config, "ttransliteratev3", &form).await?;
+
+    get_web_transliteration(&result)
+}
+
+pub fn send_sync_web_transliterate_request(
+    language: &str,
+    from_script: &str,
+    to_script: &str,
+    text: &str,
+    config: &RequestConfig<'_>,
+) -> Result<String, translator::Error> {
+    let form = transliterate_form(language, from_script, to_script, text);
+    let result = web_post_sync(config, "ttransliteratev3", &form)?;
+
+    get_web_transliteration(&result)
+}
+
+/// Translates all the texts with a single request to the Azure API.
+#[cfg(feature = "async")]
+pub async fn send_async_api_request(
+    target_language: &str,
+    source_language: &str,
+    texts: &[&str],
+    config: &RequestConfig<'_>,
+) -> Result<Vec<String>, translator::Error> {
+    let request = config.api_post(&translate_endpoint(target_language, source_language), texts);
+    let response = config.connection.transport.send_async(&request).await?;
+
+    get_translated_texts(&check_api_response(response)?)
+}
+
+/// Translates all the texts with a single request to the Azure API.
+pub fn send_sync_api_request(
+    target_language: &str,
+    source_language: &str,
+    texts: &[&str],
+    config: &RequestConfig<'_>,
+) -> Result<Vec<String>, translator::Error> {
+    let request = config.api_post(&translate_endpoint(target_language, source_language), texts);
+    let response = config.connection.transport.send_sync(&request)?;
+
+    get_translated_texts(&check_api_response(response)?)
+}
+
+#[cfg(feature = "async")]
+pub async fn send_async_api_transliterate_request(
+    language: &str,
+    from_script: &str,
+    to_script: &str,
+    texts: &[&str],
+    config: &RequestConfig<'_>,
+) -> Result<Vec<String>, translator::Error> {
+    let endpoint = transliterate_endpoint(language, from_script, to_script);
+    let request = config.api_post(&endpoint, texts);
+    let response = config.connection.transport.send_async(&request).await?;
+
+    get_api_transliterations(&check_api_response(response)?)
+}
+
+pub fn send_sync_api_transliterate_request(
+    language: &str,
+    from_script: &str,
+    to_script: &str,
+    texts: &[&str],
+    config: &RequestConfig<'_>,
+) -> Result<Vec<String>, translator::Error> {
+    let endpoint = transliterate_endpoint(language, from_script, to_script);
+    let request = config.api_post(&endpoint, texts);
+    let response = config.connection.transport.send_sync(&request)?;
+
+    get_api_transliterations(&check_api_response(response)?)
+}
+
+/// Posts the form with the token of the session, scraped again if Bing rejects it.
+#[cfg(feature = "async")]
+async fn web_post_async(
+    config: &RequestConfig<'_>,
+    endpoint: &str,
+    form: &[(&str, &str)],
+) -> Result<Value, translator::Error> {
+    let mut retried = false;
+    loop {
+        let token = match config.session.token() {
+            Some(token) => token,
+            None => {
+                let response = config
+                    .connection
+                    .transport
+                    .send_async(&config.web_page())
+                    .await?;
+                config.store_cookies(&response);
+                let token = get_web_token(response)?;
+                config.session.set_token(token.clone());
+                token
+            }
+        };
+        let request = config.web_post(endpoint, &token, form);
+        let response = config.connection.transport.send_async(&request).await?;
+        config.store_cookies(&response);
+        match check_web_response(response)? {
+            Some(result) => return Ok(result),
+            None if retried => return Err(rejected_token_error()),
+            None => {
+                config.session.expire();
+                retried = true;
+            }
+        }
+    }
+}
+
+/// Posts the form with the token of the session, scraped again if Bing rejects it.
+fn web_post_sync(
+    config: &RequestConfig<'_>,
+    endpoint: &str,
+    form: &[(&str, &str)],
+) -> Result<Value, translator::Error> {
+    let mut retried = false;
+    loop {
+        let token = match config.session.token() {
+            Some(token) => token,
+            None => {
+                let response = config.connection.transport.send_sync(&config.web_page())?;
+                config.store_cookies(&response);
+                let token = get_web_token(response)?;
+                config.session.set_token(token.clone());
+                token
+            }
+        };
+        let request = config.web_post(endpoint, &token, form);
+        let response = config.connection.transport.send_sync(&request)?;
+        config.store_cookies(&response);
+        match check_web_response(response)? {
+            Some(result) => return Ok(result),
+            None if retried => return Err(rejected_token_error()),
+            None => {
+                config.session.expire();
+                retried = true;
+            }
+        }
+    }
+}
+
+/// The web translator uses `"auto-detect"` for the detection of the source language.
+fn translate_form<'a>(
+    target_language: &'a str,
+    source_language: &'a str,
+    text: &'a str,
+) -> [(&'static str, &'a str); 3] {
+    let source_language = match source_language {
+        "" | "auto" => "auto-detect",
+        source_language => source_language,
+    };
+    [
+        ("fromLang", source_language),
+        ("to", target_language),
+        ("text", text),
+    ]
+}
+
+fn transliterate_form<'a>(
+    language: &'a str,
+    from_script: &'a str,
+    to_script: &'a str,
+    text: &'a str,
+) -> [(&'static str, &'a str); 4] {
+    [
+        ("text", text),
+        ("language", language),
+        ("fromScript", from_script),
+        ("toScript", to_script),
+    ]
+}
+
+/// No source language for the detection.
+fn translate_endpoint(target_language: &str, source_language: &str) -> String {
+    let mut endpoint = format!("translate?api-version=3.0&to={}", encode(target_language));
+    if !matches!(source_language, "" | "auto") {
+        endpoint.push_str(&format!("&from={}", encode(source_language)));
+    }
+
+    endpoint
+}
+
+fn transliterate_endpoint(language: &str, from_script: &str, to_script: &str) -> String {
+    format!(
+        "transliterate?api-version=3.0&language={}&fromScript={}&toScript={}",
+        encode(language),
+        encode(from_script),
+        encode(to_script)
+    )
+}
+
+fn get_web_token(response: Response) -> Result<WebToken, translator::Error> {
+    check_status(&response)?;
+    let page = response.body;
+    // IG:"8D4A..." ... data-iid="translator.5028" ...
+    // var params_AbusePreventionHelper = [1700000000000,"token",3600000];
+    let ig = Regex::new(r#"IG:"([^"]+)""#).unwrap();
+    let iid = Regex::new(r#"data-iid="([^"]+)""#).unwrap();
+    let helper =
+        Regex::new(r#"params_AbusePreventionHelper\s*=\s*\[\s*(\d+)\s*,\s*"([^"]+)"\s*,\s*(\d+)"#)
+            .unwrap();
+    let token = match (
+        ig.captures(&page),
+        iid.captures(&page),
+        helper.captures(&page),
+    ) {
+        (Some(ig), Some(iid), Some(helper)) => WebToken {
+            ig: ig[1].to_string(),
+            iid: iid[1].to_string(),
+            key: helper[1].to_string(),
+            token: helper[2].to_string(),
+            expires_at: Instant::now()
+                + Duration::from_millis(helper[3].parse().unwrap_or_default()),
+        },
+        _ => {
+            return Err(translator::Error::new(
+                ErrorKind::DecodeBody,
+                "token not found in the Bing page",
+            ))
+        }
+    };
+
+    Ok(token)
+}
+
+/// The web translator answers most errors with a 200 and `{"statusCode": ...}`.
+///
+/// `None` when the token is rejected, it must be scraped again.
+fn check_web_response(response: Response) -> Result<Option<Value>, translator::Error> {
+    check_status(&response)?;
+    let value: Value = serde_json::from_str(&response.body)
+        .map_err(|e| translator::Error::new(ErrorKind::DecodeBody, e.to_string()).with_source(e))?;
+    if value.get("ShowCaptcha").and_then(Value::as_bool) == Some(true) {
+        return Err(translator::Error::new(ErrorKind::Captcha, response.url));
+    }
+    let Some(status) = value.get("statusCode").and_then(Value::as_u64) else {
+        return Ok(Some(value));
+    };
+    let status = u16::try_from(status).unwrap_or(u16::MAX);
+    let message = value
+        .get("errorMessage")
+        .and_then(Value::as_str)
+        .filter(|message| !message.is_empty())
+        .map_or_else(|| response.url.clone(), str::to_string);
+    let kind = match status {
+        // expired or unknown token
+        205 => return Ok(None),
+        400 => ErrorKind::InvalidRequest,
+        429 => ErrorKind::RateLimited { retry_after: None },
+        _ => ErrorKind::Status,
+    };
+    Err(translator::Error::new(kind, message).with_status(status))
+}
+
+/// The Azure API answers the errors with `{"error": {"code": 400036, "message": "..."}}`.
+fn check_api_response(response: Response) -> Result<Value, translator::Error> {
+    let value: Option<Value> = serde_json::from_str(&response.body).ok();
+    if (200..300).contains(&response.status) {
+        return value.ok_or_else(|| translator::Error::new(ErrorKind::DecodeBody, response.body));
+    }
+
+    let error = value.as_ref().and_then(|value| value.get("error"));
+    let code = error
+        .and_then(|error| error.get("code"))
+        .and_then(Value::as_u64);
+    let message = error
+        .and_then(|error| error.get("message"))
+        .and_then(Value::as_str)
+        .map_or_else(|| response.url.clone(), str::to_string);
+    let kind = match (response.status, code) {
+        (400, _) => ErrorKind::InvalidRequest,
+        // the free tier is used up
+        (403, Some(403001)) => ErrorKind::QuotaExceeded,
+        (429, _) => shared::rate_limited(&response),
+        _ => ErrorKind::Status,
+    };
+    Err(translator::Error::new(kind, message).with_status(response.status))
+}
+
+/// Errors of the HTTP status, before the body is read.
+fn check_status(response: &Response) -> Result<(), translator::Error> {
+    if response.path().starts_with("/captcha") {
+        return Err(
+            translator::Error::new(ErrorKind::Captcha, response.url.clone())
+                .with_status(response.status),
+        );
+    }
+    let kind = match response.status {
+        200..=299 => return Ok(()),
+        429 => shared::rate_limited(response),
+        _ => ErrorKind::Status,
+    };
+    Err(translator::Error::new(kind, response.url.clone()).with_status(response.status))
+}
+
+fn rejected_token_error() -> translator::Error {
+    translator::Error::new(
+        ErrorKind::InvalidRequest,
+        "the token of the Bing page was rejected",
+    )
+}
+
+fn get_translated_texts(value: &Value) -> Result<Vec<String>, translator::Error> {
+    // [{"detectedLanguage": {...}, "translations": [{"text": "Bonjour", "to": "fr"}]}, ...]
+    value
+        .as_array()
+        .and_then(|results| {
+            results
+                .iter()
+                .map(|result| {
+                    let text = result.get("translations")?.get(0)?.get("text")?;
+                    Some(text.as_str()?.to_string())
+                })
+                .collect()
+        })
+        .ok_or_else(|| translator::Error::new(ErrorKind::DecodeBody, value.to_string()))
+}
+
+fn get_api_transliterations(value: &Value) -> Result<Vec<String>, translator::Error> {
+    // [{"text": "konnichiwa", "script": "Latn"}, ...]
+    value
+        .as_array()
+        .and_then(|results| {
+            results
+                .iter()
+                .map(|result| Some(result.get("text")?.as_str()?.to_string()))
+                .collect()
+        })
+        .ok_or_else(|| translator::Error::new(ErrorKind::DecodeBody, value.to_string()))
+}
+
+fn get_web_transliteration(value: &Value) -> Result<String, translator::Error> {
+    // "konnichiwa"
+    value
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| translator::Error::new(ErrorKind::DecodeBody, value.to_string()))
+}
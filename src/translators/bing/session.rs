@@ -0,0 +1,66 @@
+use crate::translators::transport::CookieJar;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+/// Token and cookies of the Bing web translator, scraped from its page.
+///
+/// Clones share the same session, so the page is fetched once for a translator and
+/// all its clones, then again when the token expires or is rejected.
+#[derive(Debug, Clone, Default)]
+pub struct BingSession {
+    token: Arc<Mutex<Option<WebToken>>>,
+    cookie_jar: CookieJar,
+}
+
+/// Values of the page sent with each request.
+#[derive(Debug, Clone)]
+pub(crate) struct WebToken {
+    /// `IG` of the page.
+    pub ig: String,
+    /// `data-iid` of the translator.
+    pub iid: String,
+    /// First value of `params_AbusePreventionHelper`, a timestamp.
+    pub key: String,
+    /// Second value of `params_AbusePreventionHelper`.
+    pub token: String,
+    pub expires_at: Instant,
+}
+
+impl BingSession {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Forgets the token and the cookies, the page is fetched again by the next request.
+    pub fn clear(&self) {
+        *self.token.lock().unwrap() = None;
+        self.cookie_jar.clear();
+    }
+
+    /// Whether a token is known and not expired.
+    pub fn has_token(&self) -> bool {
+        self.token().is_some()
+    }
+
+    pub(crate) fn token(&self) -> Option<WebToken> {
+        self.token
+            .lock()
+            .unwrap()
+            .as_ref()
+            .filter(|token| token.expires_at > Instant::now())
+            .cloned()
+    }
+
+    pub(crate) fn set_token(&self, token: WebToken) {
+        *self.token.lock().unwrap() = Some(token);
+    }
+
+    /// Forgets the token only, the cookies are kept.
+    pub(crate) fn expire(&self) {
+        *self.token.lock().unwrap() = None;
+    }
+
+    pub(crate) fn cookie_jar(&self) -> &CookieJar {
+        &self.cookie_jar
+    }
+}
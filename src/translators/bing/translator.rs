@@ -0,0 +1,441 @@
+use crate::translators::bing::languages;
+#[cfg(feature = "async")]
+use crate::translators::bing::requests::{
+    send_async_api_request, send_async_api_transliterate_request, send_async_web_request,
+    send_async_web_transliterate_request,
+};
+use crate::translators::bing::requests::{
+    send_sync_api_request, send_sync_api_transliterate_request, send_sync_web_request,
+    send_sync_web_transliterate_request, RequestConfig,
+};
+use crate::translators::bing::session::BingSession;
+use crate::translators::chunks::{batches, Chunking};
+use crate::translators::registry::BackendConfig;
+#[cfg(feature = "async")]
+use crate::translators::runtime;
+use crate::translators::translator::shared::{
+    self, chunking, connection, read_shared_config, validate_target,
+};
+use crate::translators::translator::{self, Language, Translator};
+use crate::translators::transport::{self, TlsConfig, Transport};
+
+use macon::Builder;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+/// Bing Translator: the web translator without key, or the Azure Translator API.
+///
+/// # Dependencies:
+/// Add to your dependency:
+/// ```no_run ignore
+/// [dependencies]
+/// translators = { version = "0.1.5", features = ["bing", "tokio-async"] }
+/// ```
+/// # Examples
+///
+/// ```no_run ignore
+/// use translators::{BingTranslator, Translator};
+///
+/// fn main() {
+///     // web translator, the token is scraped from the page like a browser
+///     let bing_trans = BingTranslator::default();
+///     let translated_text = bing_trans
+///         .translate_sync("Hello, world!", "", "es")
+///         .unwrap();
+///     println!("{}", translated_text);
+///
+///     // Azure Translator API
+///     let azure_trans = BingTranslator::builder()
+///         .api_key("xxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx")
+///         .region("westeurope")
+///         .text_limit(50000usize)
+///         .build();
+///     let translated_texts = azure_trans
+///         .translate_batch_sync(&["Hello", "Goodbye"], "en", "fr")
+///         .unwrap();
+///     let romaji = azure_trans
+///         .transliterate_sync("こんにちは", "ja", "Jpan", "Latn")
+///         .unwrap();
+/// }
+/// ```
+#[derive(Builder, Clone, Debug)]
+#[builder(mode=Panic, Default)]
+pub struct BingTranslator {
+    /// Key of the Azure Translator API, the web translator is used without it.
+    pub api_key: Option<String>,
+    /// Region of the Azure resource, needed by the regional keys.
+    pub region: Option<String>,
+    /// Url of the web translator (`https://www.bing.com`) or of the Azure API
+    /// (`https://api.cognitive.microsofttranslator.com`) by default.
+    pub url: Option<String>,
+    /// User agent of the requests, a desktop browser by default for the web translator.
+    pub user_agent: Option<String>,
+    /// Token and cookies of the web translator, shared by the clones of the translator.
+    pub session: BingSession,
+    /// How long to wait for each request, from connect to the end of the body.
+    pub request_timeout: Duration,
+    /// How long to wait for the connection to the server.
+    pub connect_timeout: Option<Duration>,
+    /// How long a whole `translate_*` call can take, all chunks included.
+    pub total_timeout: Option<Duration>,
+    /// Delay between requests.
+    pub request_delay: Duration,
+    /// Proxy address, see [`crate::GoogleTranslator`] for the format.
+    pub proxy_address: Option<String>,
+    /// How many requests can be handled concurrently.
+    ///
+    /// Unlimited in async by default. The sync API translates the chunks on up to
+    /// `max_concurrency` threads, one at a time if unset.
+    pub max_concurrency: Option<usize>,
+    /// Maximum number of chars of a request: 1000 for the web translator,
+    /// up to 50000 with the Azure API.
+    pub text_limit: usize,
+    /// Headers added to every request.
+    pub headers: Vec<(String, String)>,
+    /// Root certificates and client identity, e.g. for a TLS-inspecting proxy.
+    pub tls: Option<Arc<TlsConfig>>,
+    /// Sends the requests, reqwest by default (ureq without the `reqwest` feature).
+    #[builder(Into=!)]
+    pub transport: Arc<dyn Transport>,
+}
+
+impl translator::Translator for BingTranslator {
+    #[cfg(feature = "async")]
+    async fn translate_async(
+        &self,
+        text: &str,
+        source_language: &str,
+        target_language: &str,
+    ) -> Result<String, translator::Error> {
+        validate_target(target_language, BACKEND)?;
+        let config = self.request_config();
+        self.chunking()
+            .translate_async(text, |chunk| async move {
+                if config.api_key.is_none() {
+                    return send_async_web_request(
+                        target_language,
+                        source_language,
+                        chunk,
+                        &config,
+                    )
+                    .await;
+                }
+                let mut translated =
+                    send_async_api_request(target_language, source_language, &[chunk], &config)
+                        .await?;
+                Ok(translated.remove(0))
+            })
+            .await
+    }
+
+    fn translate_sync(
+        &self,
+        text: &str,
+        source_language: &str,
+        target_language: &str,
+    ) -> Result<String, translator::Error> {
+        validate_target(target_language, BACKEND)?;
+        self.chunking().translate_sync(text, |chunk, time_left| {
+            let mut config = self.request_config();
+            if let Some(time_left) = time_left {
+                config.connection.timeout = config.connection.timeout.min(time_left);
+            }
+            if config.api_key.is_none() {
+                return send_sync_web_request(target_language, source_language, chunk, &config);
+            }
+            let mut translated =
+                send_sync_api_request(target_language, source_language, &[chunk], &config)?;
+            Ok(translated.remove(0))
+        })
+    }
+
+    /// A part of the Bing languages, the other codes are accepted too.
+    fn supported_languages(&self) -> Vec<Language> {
+        shared::languages(languages::LANGUAGES)
+    }
+}
+
+impl BingTranslator {
+    /// Translates several texts.
+    ///
+    /// With the Azure API, the texts are sent by groups of up to 1000 and `text_limit`
+    /// chars. The web translator gets one request per text. A text longer than
+    /// `text_limit` is split in chunks like with [`Translator::translate_sync`].
+    pub fn translate_batch_sync(
+        &self,
+        texts: &[&str],
+        source_language: &str,
+        target_language: &str,
+    ) -> Result<Vec<String>, translator::Error> {
+        validate_target(target_language, BACKEND)?;
+        let mut results = Vec::with_capacity(texts.len());
+        for (index, batch) in self.batches(texts).into_iter().enumerate() {
+            if index > 0 && !self.request_delay.is_zero() {
+                thread::sleep(self.request_delay);
+            }
+            let batch_texts = &texts[batch];
+            if let [text] = batch_texts {
+                if self.api_key.is_none() || self.is_long(text) {
+                    results.push(self.translate_sync(text, source_language, target_language)?);
+                    continue;
+                }
+            }
+            let translated = send_sync_api_request(
+                target_language,
+                source_language,
+                batch_texts,
+                &self.request_config(),
+            )
+            .map_err(|e| e.with_backend(BACKEND))?;
+            results.extend(translated);
+        }
+
+        Ok(results)
+    }
+
+    /// Translates several texts.
+    ///
+    /// See [`BingTranslator::translate_batch_sync`].
+    #[cfg(feature = "async")]
+    pub async fn translate_batch_async(
+        &self,
+        texts: &[&str],
+        source_language: &str,
+        target_language: &str,
+    ) -> Result<Vec<String>, translator::Error> {
+        validate_target(target_language, BACKEND)?;
+        let mut results = Vec::with_capacity(texts.len());
+        for (index, batch) in self.batches(texts).into_iter().enumerate() {
+            if index > 0 && !self.request_delay.is_zero() {
+                runtime::sleep(self.request_delay).await;
+            }
+            let batch_texts = &texts[batch];
+            if let [text] = batch_texts {
+                if self.api_key.is_none() || self.is_long(text) {
+                    results.push(
+                        self.translate_async(text, source_language, target_language)
+                            .await?,
+                    );
+                    continue;
+                }
+            }
+            let translated = send_async_api_request(
+                target_language,
+                source_language,
+                batch_texts,
+                &self.request_config(),
+            )
+            .await
+            .map_err(|e| e.with_backend(BACKEND))?;
+            results.extend(translated);
+        }
+
+        Ok(results)
+    }
+
+    /// Converts the text of `language` from a script to another, e.g. `"ja"` from
+    /// `"Jpan"` to `"Latn"`. The text isn't split in chunks.
+    pub fn transliterate_sync(
+        &self,
+        text: &str,
+        language: &str,
+        from_script: &str,
+        to_script: &str,
+    ) -> Result<String, translator::Error> {
+        let config = self.request_config();
+        let transliterated = if config.api_key.is_none() {
+            send_sync_web_transliterate_request(language, from_script, to_script, text, &config)
+        } else {
+            send_sync_api_transliterate_request(language, from_script, to_script, &[text], &config)
+                .map(|mut transliterated| transliterated.remove(0))
+        };
+
+        transliterated.map_err(|e| e.with_backend(BACKEND))
+    }
+
+    /// Converts the text of `language` from a script to another.
+    ///
+    /// See [`BingTranslator::transliterate_sync`].
+    #[cfg(feature = "async")]
+    pub async fn transliterate_async(
+        &self,
+        text: &str,
+        language: &str,
+        from_script: &str,
+        to_script: &str,
+    ) -> Result<String, translator::Error> {
+        let config = self.request_config();
+        let transliterated = if config.api_key.is_none() {
+            send_async_web_transliterate_request(language, from_script, to_script, text, &config)
+                .await
+        } else {
+            send_async_api_transliterate_request(language, from_script, to_script, &[text], &config)
+                .await
+                .map(|mut transliterated| transliterated.remove(0))
+        };
+
+        transliterated.map_err(|e| e.with_backend(BACKEND))
+    }
+
+    /// Converts several texts, by groups of up to 10 with the Azure API.
+    pub fn transliterate_batch_sync(
+        &self,
+        texts: &[&str],
+        language: &str,
+        from_script: &str,
+        to_script: &str,
+    ) -> Result<Vec<String>, translator::Error> {
+        if self.api_key.is_none() {
+            return texts
+                .iter()
+                .map(|text| self.transliterate_sync(text, language, from_script, to_script))
+                .collect();
+        }
+        let mut results = Vec::with_capacity(texts.len());
+        for batch in batches(texts, MAX_TRANSLITERATE_TEXTS, MAX_TRANSLITERATE_CHARS) {
+            let transliterated = send_sync_api_transliterate_request(
+                language,
+                from_script,
+                to_script,
+                &texts[batch],
+                &self.request_config(),
+            )
+            .map_err(|e| e.with_backend(BACKEND))?;
+            results.extend(transliterated);
+        }
+
+        Ok(results)
+    }
+
+    /// Converts several texts.
+    ///
+    /// See [`BingTranslator::transliterate_batch_sync`].
+    #[cfg(feature = "async")]
+    pub async fn transliterate_batch_async(
+        &self,
+        texts: &[&str],
+        language: &str,
+        from_script: &str,
+        to_script: &str,
+    ) -> Result<Vec<String>, translator::Error> {
+        let mut results = Vec::with_capacity(texts.len());
+        if self.api_key.is_none() {
+            for text in texts {
+                results.push(
+                    self.transliterate_async(text, language, from_script, to_script)
+                        .await?,
+                );
+            }
+            return Ok(results);
+        }
+        for batch in batches(texts, MAX_TRANSLITERATE_TEXTS, MAX_TRANSLITERATE_CHARS) {
+            let transliterated = send_async_api_transliterate_request(
+                language,
+                from_script,
+                to_script,
+                &texts[batch],
+                &self.request_config(),
+            )
+            .await
+            .map_err(|e| e.with_backend(BACKEND))?;
+            results.extend(transliterated);
+        }
+
+        Ok(results)
+    }
+
+    /// Builds a translator from string settings, missing keys keep the default values.
+    ///
+    /// Keys are the field names: `api_key`, `region`, `url`, `user_agent`,
+    /// `request_timeout`, `connect_timeout`, `total_timeout`, `request_delay` (in seconds),
+    /// `proxy_address`, `max_concurrency` and `text_limit`.
+    pub fn from_config(config: &BackendConfig) -> Result<Self, translator::Error> {
+        let mut translator = BingTranslator::default();
+        if let Some(api_key) = config.get("api_key") {
+            translator.api_key = Some(api_key.to_string());
+        }
+        if let Some(region) = config.get("region") {
+            translator.region = Some(region.to_string());
+        }
+        if let Some(url) = config.get("url") {
+            translator.url = Some(url.to_string());
+        }
+        if let Some(user_agent) = config.get("user_agent") {
+            translator.user_agent = Some(user_agent.to_string());
+        }
+        read_shared_config!(translator, config);
+
+        Ok(translator)
+    }
+
+    /// Url of the requests: the configured one, or the one of the web translator
+    /// or of the Azure API.
+    pub fn api_url(&self) -> &str {
+        match (&self.url, &self.api_key) {
+            (Some(url), _) => url,
+            (None, None) => WEB_URL,
+            (None, Some(_)) => API_URL,
+        }
+    }
+
+    fn batches(&self, texts: &[&str]) -> Vec<std::ops::Range<usize>> {
+        if self.api_key.is_none() {
+            return (0..texts.len()).map(|index| index..index + 1).collect();
+        }
+        batches(texts, MAX_BATCH_TEXTS, self.text_limit)
+    }
+
+    fn is_long(&self, text: &str) -> bool {
+        text.chars().count() > self.text_limit
+    }
+
+    fn chunking(&self) -> Chunking {
+        chunking!(self, BACKEND)
+    }
+
+    fn request_config(&self) -> RequestConfig<'_> {
+        RequestConfig {
+            connection: connection!(self),
+            url: self.api_url(),
+            api_key: self.api_key.as_deref(),
+            region: self.region.as_deref(),
+            user_agent: self.user_agent.as_deref(),
+            session: &self.session,
+        }
+    }
+}
+
+impl Default for BingTranslator {
+    fn default() -> Self {
+        BingTranslator {
+            api_key: None,
+            region: None,
+            url: None,
+            user_agent: Some(DEFAULT_USER_AGENT.to_string()),
+            session: BingSession::new(),
+            request_timeout: Duration::from_secs(35),
+            connect_timeout: None,
+            total_timeout: None,
+            request_delay: Duration::ZERO,
+            proxy_address: None,
+            max_concurrency: None,
+            text_limit: 1000,
+            headers: Vec::new(),
+            tls: None,
+            transport: transport::default_transport(),
+        }
+    }
+}
+
+/// Name of the translator in the errors.
+const BACKEND: &str = "bing";
+const WEB_URL: &str = "https://www.bing.com";
+const API_URL: &str = "https://api.cognitive.microsofttranslator.com";
+const DEFAULT_USER_AGENT: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 \
+    (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36";
+/// Maximum number of texts of a translation request to the Azure API.
+const MAX_BATCH_TEXTS: usize = 1000;
+/// Limits of a transliteration request to the Azure API.
+const MAX_TRANSLITERATE_TEXTS: usize = 10;
+const MAX_TRANSLITERATE_CHARS: usize = 5000;
@@ -0,0 +1,291 @@
+//! Translation of the chunks of a text, one at a time or concurrently.
+
+use super::{split_chunks, Chunk};
+#[cfg(feature = "async")]
+use crate::translators::runtime;
+use crate::translators::translator::{Error, ErrorKind};
+#[cfg(feature = "async")]
+use async_lock::Semaphore;
+#[cfg(feature = "async")]
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+#[cfg(feature = "async")]
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How the chunks of a text are sent, from the settings of a translator.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Chunking {
+    /// Name of the translator in the errors.
+    pub backend: &'static str,
+    /// Maximum number of chars of a chunk.
+    pub text_limit: usize,
+    /// Delay between requests, the chunks are sent one at a time if set.
+    pub request_delay: Duration,
+    /// How many chunks are translated at once, unlimited in async and one in sync if unset.
+    pub max_concurrency: Option<usize>,
+    /// How long the whole translation can take.
+    pub total_timeout: Option<Duration>,
+}
+
+impl Chunking {
+    /// Translates the chunks of `text` and joins them in order.
+    ///
+    /// `translate` gets a chunk and the time left before the total timeout, if any.
+    pub fn translate_sync<F>(&self, text: &str, translate: F) -> Result<String, Error>
+    where
+        F: Fn(&str, Option<Duration>) -> Result<String, Error> + Sync,
+    {
+        let deadline = self
+            .total_timeout
+            .map(|total_timeout| Instant::now() + total_timeout);
+        let chunks = split_chunks(text, self.text_limit);
+        let workers = self
+            .max_concurrency
+            .unwrap_or(1)
+            .clamp(1, chunks.len().max(1));
+        // send sequential req with a delay
+        if !self.request_delay.is_zero() || workers == 1 {
+            let mut result = String::new();
+            for (index, chunk) in chunks.iter().enumerate() {
+                let translated_chunk =
+                    self.translate_indexed_chunk_sync(text, index, chunk, deadline, &translate)?;
+
+                if !self.request_delay.is_zero() {
+                    thread::sleep(self.request_delay);
+                }
+
+                result.push_str(&translated_chunk);
+            }
+
+            return Ok(result);
+        }
+
+        // translate on `workers` threads, each one takes the next chunk
+        let next_chunk = AtomicUsize::new(0);
+        let failed = AtomicBool::new(false);
+        let results = Mutex::new(vec![None; chunks.len()]);
+        thread::scope(|scope| {
+            for _ in 0..workers {
+                scope.spawn(|| {
+                    while !failed.load(Ordering::Relaxed) {
+                        let index = next_chunk.fetch_add(1, Ordering::Relaxed);
+                        let Some(chunk) = chunks.get(index) else {
+                            break;
+                        };
+                        let translated_chunk = self
+                            .translate_indexed_chunk_sync(text, index, chunk, deadline, &translate);
+                        if translated_chunk.is_err() {
+                            failed.store(true, Ordering::Relaxed);
+                        }
+                        results.lock().unwrap()[index] = Some(translated_chunk);
+                    }
+                });
+            }
+        });
+
+        // chunks are taken in order, every chunk before the first error has a result
+        let mut result = String::new();
+        for translated_chunk in results.into_inner().unwrap().into_iter().map_while(|r| r) {
+            result.push_str(&translated_chunk?);
+        }
+
+        Ok(result)
+    }
+
+    fn translate_indexed_chunk_sync<F>(
+        &self,
+        text: &str,
+        index: usize,
+        chunk: &Chunk,
+        deadline: Option<Instant>,
+        translate: &F,
+    ) -> Result<String, Error>
+    where
+        F: Fn(&str, Option<Duration>) -> Result<String, Error>,
+    {
+        let time_left = deadline.map(|deadline| deadline.saturating_duration_since(Instant::now()));
+        if time_left.is_some_and(|time_left| time_left.is_zero()) {
+            return Err(self.total_timeout_error());
+        }
+        translate(&text[chunk.range()], time_left).map_err(|e| {
+            e.with_backend(self.backend)
+                .with_chunk(index, chunk.range())
+        })
+    }
+
+    /// Translates the chunks of `text` and joins them in order.
+    #[cfg(feature = "async")]
+    pub async fn translate_async<'a, F, Fut>(
+        &self,
+        text: &'a str,
+        translate: F,
+    ) -> Result<String, Error>
+    where
+        F: Fn(&'a str) -> Fut,
+        Fut: Future<Output = Result<String, Error>>,
+    {
+        let translation = self.translate_chunks_async(text, translate);
+        match self.total_timeout {
+            Some(total_timeout) => runtime::timeout(total_timeout, translation)
+                .await
+                .ok_or_else(|| self.total_timeout_error())?,
+            None => translation.await,
+        }
+    }
+
+    #[cfg(feature = "async")]
+    async fn translate_chunks_async<'a, F, Fut>(
+        &self,
+        text: &'a str,
+        translate: F,
+    ) -> Result<String, Error>
+    where
+        F: Fn(&'a str) -> Fut,
+        Fut: Future<Output = Result<String, Error>>,
+    {
+        let mut result = String::new();
+        let mut tasks = Vec::new();
+        let semaphore = self
+            .max_concurrency
+            .map(|max| Arc::new(Semaphore::new(max)));
+        let chunks = split_chunks(text, self.text_limit);
+        for (index, chunk) in chunks.into_iter().enumerate() {
+            let semaphore = semaphore.clone();
+            let translation = translate(&text[chunk.range()]);
+
+            let task = async move {
+                let _permit = match &semaphore {
+                    Some(sem) => Some(sem.acquire().await),
+                    None => None,
+                };
+
+                translation.await.map_err(|e| {
+                    e.with_backend(self.backend)
+                        .with_chunk(index, chunk.range())
+                })
+            };
+
+            tasks.push(task);
+        }
+
+        // send sequential req with a delay
+        if !self.request_delay.is_zero() {
+            for task in tasks {
+                match task.await {
+                    Ok(translated_chunk) => result.push_str(&translated_chunk),
+                    Err(e) => return Err(e),
+                }
+                runtime::sleep(self.request_delay).await;
+            }
+        // send all req at once
+        } else {
+            let results = futures::future::join_all(tasks).await;
+
+            for res in results {
+                match res {
+                    Ok(translated_chunk) => result.push_str(&translated_chunk),
+                    Err(e) => return Err(e),
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Translates the chunks of `text` one at a time and joins them in order.
+    ///
+    /// `translate` also gets up to `context` previous chunks with their translations,
+    /// the oldest first.
+    #[cfg(feature = "llm")]
+    pub fn translate_in_context_sync<F>(
+        &self,
+        text: &str,
+        context: usize,
+        translate: F,
+    ) -> Result<String, Error>
+    where
+        F: Fn(&str, &[(&str, String)], Option<Duration>) -> Result<String, Error>,
+    {
+        let deadline = self
+            .total_timeout
+            .map(|total_timeout| Instant::now() + total_timeout);
+        let mut translated_chunks: Vec<(&str, String)> = Vec::new();
+        for (index, chunk) in split_chunks(text, self.text_limit).iter().enumerate() {
+            if index > 0 && !self.request_delay.is_zero() {
+                thread::sleep(self.request_delay);
+            }
+            let previous = &translated_chunks[translated_chunks.len().saturating_sub(context)..];
+            let translated_chunk = self.translate_indexed_chunk_sync(
+                text,
+                index,
+                chunk,
+                deadline,
+                &|chunk, time_left| translate(chunk, previous, time_left),
+            )?;
+            translated_chunks.push((&text[chunk.range()], translated_chunk));
+        }
+
+        Ok(translated_chunks
+            .into_iter()
+            .map(|(_, translated_chunk)| translated_chunk)
+            .collect())
+    }
+
+    /// Translates the chunks of `text` one at a time and joins them in order.
+    ///
+    /// See [`Chunking::translate_in_context_sync`].
+    #[cfg(all(feature = "llm", feature = "async"))]
+    pub async fn translate_in_context_async<'a, F, Fut>(
+        &self,
+        text: &'a str,
+        context: usize,
+        translate: F,
+    ) -> Result<String, Error>
+    where
+        F: Fn(&'a str, Vec<(&'a str, String)>) -> Fut,
+        Fut: Future<Output = Result<String, Error>>,
+    {
+        let translation = async {
+            let mut translated_chunks: Vec<(&str, String)> = Vec::new();
+            for (index, chunk) in split_chunks(text, self.text_limit).iter().enumerate() {
+                if index > 0 && !self.request_delay.is_zero() {
+                    runtime::sleep(self.request_delay).await;
+                }
+                let previous =
+                    &translated_chunks[translated_chunks.len().saturating_sub(context)..];
+                let translated_chunk = translate(&text[chunk.range()], previous.to_vec())
+                    .await
+                    .map_err(|e| {
+                        e.with_backend(self.backend)
+                            .with_chunk(index, chunk.range())
+                    })?;
+                translated_chunks.push((&text[chunk.range()], translated_chunk));
+            }
+
+            Ok(translated_chunks
+                .into_iter()
+                .map(|(_, translated_chunk)| translated_chunk)
+                .collect())
+        };
+        match self.total_timeout {
+            Some(total_timeout) => runtime::timeout(total_timeout, translation)
+                .await
+                .ok_or_else(|| self.total_timeout_error())?,
+            None => translation.await,
+        }
+    }
+
+    fn total_timeout_error(&self) -> Error {
+        Error::new(
+            ErrorKind::Timeout,
+            format!(
+                "translation took more than {:?}",
+                self.total_timeout.unwrap_or_default()
+            ),
+        )
+        .with_backend(self.backend)
+    }
+}
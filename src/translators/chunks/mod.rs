@@ -0,0 +1,116 @@
+//! Splitting of long texts in chunks.
+
+#[cfg(any(
+    feature = "google",
+    feature = "libre",
+    feature = "deepl",
+    feature = "bing",
+    feature = "yandex",
+    feature = "mymemory",
+    feature = "lingva",
+    feature = "llm"
+))]
+mod chunking;
+
+use std::ops::Range;
+
+#[cfg(any(
+    feature = "google",
+    feature = "libre",
+    feature = "deepl",
+    feature = "bing",
+    feature = "yandex",
+    feature = "mymemory",
+    feature = "lingva",
+    feature = "llm"
+))]
+pub(crate) use self::chunking::Chunking;
+
+/// First chunk of the text, enough to detect its language.
+#[cfg(any(feature = "google", feature = "libre"))]
+pub(crate) fn detection_sample(text: &str, text_limit: usize) -> &str {
+    match split_chunks(text, text_limit).first() {
+        Some(chunk) => &text[chunk.range()],
+        None => text,
+    }
+}
+
+/// Groups of consecutive texts sent with a single request, up to `max_texts` texts
+/// and `text_limit` chars. A longer text is alone in its group.
+#[cfg(any(feature = "deepl", feature = "bing"))]
+pub(crate) fn batches(texts: &[&str], max_texts: usize, text_limit: usize) -> Vec<Range<usize>> {
+    let mut batches = Vec::new();
+    let mut start = 0;
+    let mut chars_count = 0;
+    for (index, text) in texts.iter().enumerate() {
+        let text_chars = text.chars().count();
+        let full = index - start == max_texts || chars_count + text_chars > text_limit;
+        if index > start && full {
+            batches.push(start..index);
+            start = index;
+            chars_count = 0;
+        }
+        chars_count += text_chars;
+    }
+    if start < texts.len() {
+        batches.push(start..texts.len());
+    }
+
+    batches
+}
+
+pub(crate) fn split_chunks(text: &str, text_limit: usize) -> Vec<Chunk> {
+    let delimiters = &[
+        ' ', '\n', '\r', '\t', '.', ',', ':', ';', '"', '(', ')', '[', ']', '{', '}', '/',
+    ];
+    let mut chunks = vec![];
+    let mut start_index = 0;
+    let mut chars_count = 0;
+    let mut last_delimiter_pos = None;
+
+    for (char_i, char) in text.char_indices() {
+        chars_count += 1;
+
+        if delimiters.contains(&char) {
+            last_delimiter_pos = Some((char_i + char.len_utf8(), chars_count));
+        }
+
+        if chars_count == text_limit {
+            let (end, reset_count) = match last_delimiter_pos {
+                Some((delimiter_pos, delimiter_count)) => (delimiter_pos, delimiter_count),
+                None => (char_i, chars_count),
+            };
+
+            chunks.push(Chunk {
+                start: start_index,
+                end,
+            });
+
+            start_index = end;
+            chars_count -= reset_count;
+            last_delimiter_pos = None;
+        }
+    }
+
+    if start_index < text.len() {
+        chunks.push(Chunk {
+            start: start_index,
+            end: text.len(),
+        });
+    }
+
+    chunks
+}
+
+/// Part of a text, in bytes.
+#[derive(Default, Debug, Clone, Copy)]
+pub(crate) struct Chunk {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Chunk {
+    pub fn range(&self) -> Range<usize> {
+        self.start..self.end
+    }
+}
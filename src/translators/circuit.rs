@@ -0,0 +1,206 @@
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Stops the requests to a failing service for a while.
+///
+/// The circuit opens after `failure_threshold` failures in a row: the requests are
+/// refused during the cooldown. Then it is half-open, a single probe request goes out
+/// and its result closes or opens the circuit again. Clones share the same state.
+///
+/// ```ignore
+/// let breaker = CircuitBreaker::new(5, Duration::from_secs(60));
+/// if let Some(permit) = breaker.try_acquire() {
+///     match send_request() {
+///         Ok(_) => permit.record_success(),
+///         Err(_) => permit.record_failure(),
+///     }
+/// }
+/// ```
+#[derive(Clone)]
+pub struct CircuitBreaker {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    failure_threshold: u32,
+    cooldown: Duration,
+    state: Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+    /// A probe request of the half-open circuit is in flight.
+    probing: bool,
+}
+
+/// A request allowed by a [`CircuitBreaker`].
+///
+/// Dropping the permit of a probe without recording its result, e.g. when the
+/// request is cancelled, lets another probe go out.
+pub struct CircuitPermit {
+    breaker: CircuitBreaker,
+    /// Opening of the circuit this permit probes, `None` if it isn't a probe.
+    probe_of: Option<Instant>,
+}
+
+/// State of a [`CircuitBreaker`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// The requests go out.
+    Closed,
+    /// The requests are refused until the end of the cooldown.
+    Open,
+    /// The cooldown is over, a single probe request can go out.
+    HalfOpen,
+}
+
+impl CircuitBreaker {
+    /// Breaker opening after `failure_threshold` failures in a row (at least 1),
+    /// for `cooldown`.
+    pub fn new(failure_threshold: u32, cooldown: Duration) -> Self {
+        CircuitBreaker {
+            inner: Arc::new(Inner {
+                failure_threshold: failure_threshold.max(1),
+                cooldown,
+                state: Mutex::new(State::default()),
+            }),
+        }
+    }
+
+    pub fn state(&self) -> CircuitState {
+        let state = self.inner.state.lock().unwrap();
+        self.state_of(&state)
+    }
+
+    /// A permit if a request can go out. In the half-open state, only the first caller
+    /// gets one until its probe is recorded or dropped.
+    pub fn try_acquire(&self) -> Option<CircuitPermit> {
+        let mut state = self.inner.state.lock().unwrap();
+        let probe_of = match self.state_of(&state) {
+            CircuitState::Closed => None,
+            CircuitState::Open => return None,
+            CircuitState::HalfOpen if state.probing => return None,
+            CircuitState::HalfOpen => {
+                state.probing = true;
+                state.opened_at
+            }
+        };
+        Some(CircuitPermit {
+            breaker: self.clone(),
+            probe_of,
+        })
+    }
+
+    /// The service answered, the circuit is closed.
+    pub fn record_success(&self) {
+        *self.inner.state.lock().unwrap() = State::default();
+    }
+
+    /// The service failed, the circuit opens at the threshold or if the probe failed.
+    pub fn record_failure(&self) {
+        let mut state = self.inner.state.lock().unwrap();
+        state.consecutive_failures = state.consecutive_failures.saturating_add(1);
+        let open = match self.state_of(&state) {
+            CircuitState::Closed => state.consecutive_failures >= self.inner.failure_threshold,
+            CircuitState::HalfOpen => state.probing,
+            CircuitState::Open => false,
+        };
+        if open {
+            state.opened_at = Some(Instant::now());
+            state.probing = false;
+        }
+    }
+
+    /// Closes the circuit, e.g. after a change of proxy.
+    pub fn reset(&self) {
+        self.record_success();
+    }
+
+    /// Number of failures since the last success.
+    pub fn consecutive_failures(&self) -> u32 {
+        self.inner.state.lock().unwrap().consecutive_failures
+    }
+
+    /// Time left before the half-open state, `None` if the circuit isn't open.
+    pub fn retry_after(&self) -> Option<Duration> {
+        let state = self.inner.state.lock().unwrap();
+        let opened_at = state.opened_at?;
+        let left = self.inner.cooldown.saturating_sub(opened_at.elapsed());
+        (!left.is_zero()).then_some(left)
+    }
+
+    pub fn failure_threshold(&self) -> u32 {
+        self.inner.failure_threshold
+    }
+
+    pub fn cooldown(&self) -> Duration {
+        self.inner.cooldown
+    }
+
+    fn state_of(&self, state: &State) -> CircuitState {
+        match state.opened_at {
+            None => CircuitState::Closed,
+            Some(opened_at) if opened_at.elapsed() < self.inner.cooldown => CircuitState::Open,
+            Some(_) => CircuitState::HalfOpen,
+        }
+    }
+}
+
+impl CircuitPermit {
+    /// Whether this request is the probe of a half-open circuit.
+    pub fn is_probe(&self) -> bool {
+        self.probe_of.is_some()
+    }
+
+    /// The service answered, see [`CircuitBreaker::record_success`].
+    pub fn record_success(&self) {
+        self.breaker.record_success();
+    }
+
+    /// The service failed, see [`CircuitBreaker::record_failure`].
+    pub fn record_failure(&self) {
+        self.breaker.record_failure();
+    }
+}
+
+impl Drop for CircuitPermit {
+    fn drop(&mut self) {
+        let Some(probe_of) = self.probe_of else {
+            return;
+        };
+        // still probing the same opening: the result was never recorded
+        let mut state = self.breaker.inner.state.lock().unwrap();
+        if state.opened_at == Some(probe_of) {
+            state.probing = false;
+        }
+    }
+}
+
+/// 5 failures in a row and a cooldown of 1 minute.
+impl Default for CircuitBreaker {
+    fn default() -> Self {
+        CircuitBreaker::new(5, Duration::from_secs(60))
+    }
+}
+
+impl fmt::Debug for CircuitBreaker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CircuitBreaker")
+            .field("state", &self.state())
+            .field("consecutive_failures", &self.consecutive_failures())
+            .field("failure_threshold", &self.inner.failure_threshold)
+            .field("cooldown", &self.inner.cooldown)
+            .finish()
+    }
+}
+
+impl fmt::Debug for CircuitPermit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CircuitPermit")
+            .field("is_probe", &self.is_probe())
+            .finish()
+    }
+}
@@ -0,0 +1,260 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::{mpsc, Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+/// Concurrency limit following the answers of a service, additive increase and
+/// multiplicative decrease (AIMD).
+///
+/// The limit starts at `min`. Each success faster than the latency threshold adds
+/// `1 / limit`, about one more request per round of requests, up to `max`. A 429,
+/// a captcha or a timeout halves the limit, down to `min`: the other requests already
+/// in flight saw the same overload and don't halve it again. Clones share the same limit.
+///
+/// ```ignore
+/// let concurrency = AdaptiveConcurrency::new(2, 32)
+///     .with_latency_threshold(Duration::from_secs(2));
+/// let permit = concurrency.acquire_sync();
+/// match send_request() {
+///     Ok(_) => permit.record_success(),
+///     Err(e) if e.kind() == &ErrorKind::Captcha => permit.record_overload(),
+///     Err(_) => {}
+/// }
+/// ```
+#[derive(Clone)]
+pub struct AdaptiveConcurrency {
+    min: usize,
+    max: usize,
+    latency_threshold: Option<Duration>,
+    state: Arc<Mutex<State>>,
+}
+
+struct State {
+    limit: f64,
+    in_flight: usize,
+    last_decrease: Option<Instant>,
+    /// Requests waiting for a permit, in order.
+    waiters: VecDeque<Waiter>,
+}
+
+enum Waiter {
+    Sync(mpsc::SyncSender<ConcurrencyPermit>),
+    #[cfg(feature = "async")]
+    Async(futures::channel::oneshot::Sender<ConcurrencyPermit>),
+}
+
+/// A request in flight of an [`AdaptiveConcurrency`], released when dropped.
+pub struct ConcurrencyPermit {
+    /// `None` once released.
+    concurrency: Option<AdaptiveConcurrency>,
+    granted_at: Instant,
+}
+
+impl AdaptiveConcurrency {
+    /// Limit between `min` (at least 1) and `max` requests at once, starting at `min`.
+    pub fn new(min: usize, max: usize) -> Self {
+        let min = min.max(1);
+        AdaptiveConcurrency {
+            min,
+            max: max.max(min),
+            latency_threshold: None,
+            state: Arc::new(Mutex::new(State {
+                limit: min as f64,
+                in_flight: 0,
+                last_decrease: None,
+                waiters: VecDeque::new(),
+            })),
+        }
+    }
+
+    /// The limit stops growing while the requests take longer than `latency_threshold`.
+    pub fn with_latency_threshold(mut self, latency_threshold: Duration) -> Self {
+        self.latency_threshold = Some(latency_threshold);
+        self
+    }
+
+    /// How many requests can be in flight now.
+    pub fn limit(&self) -> usize {
+        self.lock().limit as usize
+    }
+
+    /// How many requests are in flight.
+    pub fn in_flight(&self) -> usize {
+        self.lock().in_flight
+    }
+
+    pub fn min(&self) -> usize {
+        self.min
+    }
+
+    pub fn max(&self) -> usize {
+        self.max
+    }
+
+    pub fn latency_threshold(&self) -> Option<Duration> {
+        self.latency_threshold
+    }
+
+    /// A permit if the limit isn't reached and nobody is waiting.
+    pub fn try_acquire(&self) -> Option<ConcurrencyPermit> {
+        let mut state = self.lock();
+        (state.waiters.is_empty() && state.has_room()).then(|| self.grant(&mut state))
+    }
+
+    /// Blocks until a permit is free, the waiting requests get them in order.
+    pub fn acquire_sync(&self) -> ConcurrencyPermit {
+        let receiver = {
+            let mut state = self.lock();
+            if state.waiters.is_empty() && state.has_room() {
+                return self.grant(&mut state);
+            }
+            let (sender, receiver) = mpsc::sync_channel(1);
+            state.waiters.push_back(Waiter::Sync(sender));
+            receiver
+        };
+        receiver
+            .recv()
+            .expect("the waiters are kept until they get a permit")
+    }
+
+    /// Waits until a permit is free, the waiting requests get them in order.
+    #[cfg(feature = "async")]
+    pub async fn acquire_async(&self) -> ConcurrencyPermit {
+        let receiver = {
+            let mut state = self.lock();
+            if state.waiters.is_empty() && state.has_room() {
+                return self.grant(&mut state);
+            }
+            let (sender, receiver) = futures::channel::oneshot::channel();
+            state.waiters.push_back(Waiter::Async(sender));
+            receiver
+        };
+        receiver
+            .await
+            .expect("the waiters are kept until they get a permit")
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap()
+    }
+
+    fn grant(&self, state: &mut State) -> ConcurrencyPermit {
+        state.in_flight += 1;
+        ConcurrencyPermit {
+            concurrency: Some(self.clone()),
+            granted_at: Instant::now(),
+        }
+    }
+
+    /// Hands the free permits to the waiters, skipping the ones that gave up.
+    ///
+    /// The permits are sent out of the lock: a permit sent to a waiter that gives up at
+    /// the same time is dropped with the channel, and its release takes the lock again.
+    fn wake(&self) {
+        loop {
+            let (waiter, permit) = {
+                let mut state = self.lock();
+                if !state.has_room() {
+                    return;
+                }
+                let Some(waiter) = state.waiters.pop_front() else {
+                    return;
+                };
+                let permit = self.grant(&mut state);
+                (waiter, permit)
+            };
+            let unused = match waiter {
+                Waiter::Sync(sender) => sender.send(permit).err().map(|e| e.0),
+                #[cfg(feature = "async")]
+                Waiter::Async(sender) => sender.send(permit).err(),
+            };
+            if let Some(mut permit) = unused {
+                // released here, the next waiter gets the room
+                permit.concurrency = None;
+                self.lock().in_flight -= 1;
+            }
+        }
+    }
+
+    fn release(&self) {
+        self.lock().in_flight -= 1;
+        self.wake();
+    }
+
+    fn record_success(&self, latency: Duration) {
+        if self
+            .latency_threshold
+            .is_some_and(|latency_threshold| latency > latency_threshold)
+        {
+            return;
+        }
+        {
+            let mut state = self.lock();
+            state.limit = (state.limit + 1.0 / state.limit).min(self.max as f64);
+        }
+        self.wake();
+    }
+
+    fn record_overload(&self, granted_at: Instant) {
+        let mut state = self.lock();
+        if state
+            .last_decrease
+            .is_some_and(|last_decrease| granted_at < last_decrease)
+        {
+            return;
+        }
+        state.limit = (state.limit / 2.0).max(self.min as f64);
+        state.last_decrease = Some(Instant::now());
+    }
+}
+
+impl State {
+    fn has_room(&self) -> bool {
+        self.in_flight < self.limit as usize
+    }
+}
+
+impl ConcurrencyPermit {
+    /// The service answered, the limit grows if it answered fast enough.
+    pub fn record_success(&self) {
+        if let Some(concurrency) = &self.concurrency {
+            concurrency.record_success(self.granted_at.elapsed());
+        }
+    }
+
+    /// The service is overloaded or throttles the requests (429, captcha, timeout),
+    /// the limit is halved.
+    pub fn record_overload(&self) {
+        if let Some(concurrency) = &self.concurrency {
+            concurrency.record_overload(self.granted_at);
+        }
+    }
+}
+
+impl Drop for ConcurrencyPermit {
+    fn drop(&mut self) {
+        if let Some(concurrency) = self.concurrency.take() {
+            concurrency.release();
+        }
+    }
+}
+
+impl fmt::Debug for AdaptiveConcurrency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AdaptiveConcurrency")
+            .field("limit", &self.limit())
+            .field("in_flight", &self.in_flight())
+            .field("min", &self.min)
+            .field("max", &self.max)
+            .field("latency_threshold", &self.latency_threshold)
+            .finish()
+    }
+}
+
+impl fmt::Debug for ConcurrencyPermit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConcurrencyPermit")
+            .field("granted_at", &self.granted_at)
+            .finish()
+    }
+}
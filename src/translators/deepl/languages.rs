@@ -0,0 +1,38 @@
+/// Target languages of DeepL, `(code, name)`.
+///
+/// The source languages are the same without the regional variants.
+pub(crate) const LANGUAGES: &[(&str, &str)] = &[
+    ("AR", "Arabic"),
+    ("BG", "Bulgarian"),
+    ("CS", "Czech"),
+    ("DA", "Danish"),
+    ("DE", "German"),
+    ("EL", "Greek"),
+    ("EN-GB", "English (British)"),
+    ("EN-US", "English (American)"),
+    ("ES", "Spanish"),
+    ("ET", "Estonian"),
+    ("FI", "Finnish"),
+    ("FR", "French"),
+    ("HU", "Hungarian"),
+    ("ID", "Indonesian"),
+    ("IT", "Italian"),
+    ("JA", "Japanese"),
+    ("KO", "Korean"),
+    ("LT", "Lithuanian"),
+    ("LV", "Latvian"),
+    ("NB", "Norwegian (Bokmål)"),
+    ("NL", "Dutch"),
+    ("PL", "Polish"),
+    ("PT-BR", "Portuguese (Brazilian)"),
+    ("PT-PT", "Portuguese (European)"),
+    ("RO", "Romanian"),
+    ("RU", "Russian"),
+    ("SK", "Slovak"),
+    ("SL", "Slovenian"),
+    ("SV", "Swedish"),
+    ("TR", "Turkish"),
+    ("UK", "Ukrainian"),
+    ("ZH-HANS", "Chinese (simplified)"),
+    ("ZH-HANT", "Chinese (traditional)"),
+];
@@ -0,0 +1,3 @@
+pub(crate) mod languages;
+mod requests;
+pub(crate) mod translator;
@@ -0,0 +1,220 @@
+use crate::translators::deepl::translator::{DeepLUsage, Formality, TagHandling};
+use crate::translators::translator::shared::{self, Connection};
+use crate::translators::translator::{self, ErrorKind, Language};
+use crate::translators::transport::{Request, Response};
+use serde_json::{json, Value};
+
+/// Settings of a single request.
+#[derive(Debug, Clone, Copy)]
+pub struct RequestConfig<'a> {
+    pub connection: Connection<'a>,
+    /// Url of the API, without the version and the endpoint.
+    pub url: &'a str,
+    pub api_key: &'a str,
+    pub formality: Option<Formality>,
+    pub glossary_id: Option<&'a str>,
+    pub tag_handling: Option<TagHandling>,
+}
+
+impl RequestConfig<'_> {
+    fn request(&self, request: Request) -> Request {
+        self.connection
+            .request(request)
+            .header("Authorization", format!("DeepL-Auth-Key {}", self.api_key))
+            .header("Accept", "application/json")
+    }
+
+    fn post(&self, endpoint: &str, body: Value) -> Request {
+        self.request(Request::post(self.endpoint_url(endpoint), body.to_string()))
+            .header("Content-Type", "application/json")
+    }
+
+    fn get(&self, endpoint: &str) -> Request {
+        self.request(Request::get(self.endpoint_url(endpoint)))
+    }
+
+    fn endpoint_url(&self, endpoint: &str) -> String {
+        format!("{}/v2/{}", self.url.trim_end_matches('/'), endpoint)
+    }
+
+    fn translate_body(
+        &self,
+        target_language: &str,
+        source_language: &str,
+        texts: &[&str],
+    ) -> Value {
+        let mut body = json!({
+            "text": texts,
+            "target_lang": target_code(target_language),
+        });
+        // no source language for the detection
+        if !matches!(source_language, "" | "auto") {
+            body["source_lang"] = Value::from(source_language.to_ascii_uppercase());
+        }
+        if let Some(formality) = self.formality {
+            body["formality"] = Value::from(formality.as_str());
+        }
+        if let Some(glossary_id) = self.glossary_id {
+            body["glossary_id"] = Value::from(glossary_id);
+        }
+        if let Some(tag_handling) = self.tag_handling {
+            body["tag_handling"] = Value::from(tag_handling.as_str());
+        }
+
+        body
+    }
+}
+
+/// Code of a target language, with the variant DeepL requires for English and Portuguese.
+fn target_code(target_language: &str) -> String {
+    match target_language.to_ascii_uppercase().as_str() {
+        "EN" => "EN-US".to_string(),
+        "PT" => "PT-PT".to_string(),
+        target_language => target_language.to_string(),
+    }
+}
+
+/// Translates all the texts with a single request.
+#[cfg(feature = "async")]
+pub async fn send_async_request(
+    target_language: &str,
+    source_language: &str,
+    texts: &[&str],
+    config: &RequestConfig<'_>,
+) -> Result<Vec<String>, translator::Error> {
+    let request = config.post(
+        "translate",
+        config.translate_body(target_language, source_language, texts),
+    );
+    let response = config.connection.transport.send_async(&request).await?;
+
+    get_translated_texts(&check_response(response)?, texts.len())
+}
+
+/// Translates all the texts with a single request.
+pub fn send_sync_request(
+    target_language: &str,
+    source_language: &str,
+    texts: &[&str],
+    config: &RequestConfig<'_>,
+) -> Result<Vec<String>, translator::Error> {
+    let request = config.post(
+        "translate",
+        config.translate_body(target_language, source_language, texts),
+    );
+    let response = config.connection.transport.send_sync(&request)?;
+
+    get_translated_texts(&check_response(response)?, texts.len())
+}
+
+#[cfg(feature = "async")]
+pub async fn send_async_usage_request(
+    config: &RequestConfig<'_>,
+) -> Result<DeepLUsage, translator::Error> {
+    let request = config.get("usage");
+    let response = config.connection.transport.send_async(&request).await?;
+
+    get_usage(&check_response(response)?)
+}
+
+pub fn send_sync_usage_request(
+    config: &RequestConfig<'_>,
+) -> Result<DeepLUsage, translator::Error> {
+    let request = config.get("usage");
+    let response = config.connection.transport.send_sync(&request)?;
+
+    get_usage(&check_response(response)?)
+}
+
+#[cfg(feature = "async")]
+pub async fn send_async_languages_request(
+    config: &RequestConfig<'_>,
+) -> Result<Vec<Language>, translator::Error> {
+    let request = config.get("languages?type=target");
+    let response = config.connection.transport.send_async(&request).await?;
+
+    get_languages(&check_response(response)?)
+}
+
+pub fn send_sync_languages_request(
+    config: &RequestConfig<'_>,
+) -> Result<Vec<Language>, translator::Error> {
+    let request = config.get("languages?type=target");
+    let response = config.connection.transport.send_sync(&request)?;
+
+    get_languages(&check_response(response)?)
+}
+
+/// DeepL answers the errors with `{"message": "..."}`, 456 when the quota is used up.
+fn check_response(response: Response) -> Result<Value, translator::Error> {
+    let value: Option<Value> = serde_json::from_str(&response.body).ok();
+    if (200..300).contains(&response.status) {
+        return value.ok_or_else(|| translator::Error::new(ErrorKind::DecodeBody, response.body));
+    }
+
+    let message = value
+        .as_ref()
+        .and_then(|value| value.get("message"))
+        .and_then(Value::as_str)
+        .map_or_else(|| response.url.clone(), str::to_string);
+    let kind = match response.status {
+        400 | 413 | 414 => ErrorKind::InvalidRequest,
+        429 | 529 => shared::rate_limited(&response),
+        456 => ErrorKind::QuotaExceeded,
+        _ => ErrorKind::Status,
+    };
+    Err(translator::Error::new(kind, message).with_status(response.status))
+}
+
+fn get_translated_texts(value: &Value, count: usize) -> Result<Vec<String>, translator::Error> {
+    // {"translations": [{"detected_source_language": "EN", "text": "Hallo"}, ...]}
+    let texts: Option<Vec<String>> = value
+        .get("translations")
+        .and_then(Value::as_array)
+        .and_then(|translations| {
+            translations
+                .iter()
+                .map(|translation| Some(translation.get("text")?.as_str()?.to_string()))
+                .collect()
+        });
+
+    match texts {
+        Some(texts) if texts.len() == count => Ok(texts),
+        _ => Err(translator::Error::new(
+            ErrorKind::DecodeBody,
+            value.to_string(),
+        )),
+    }
+}
+
+fn get_usage(value: &Value) -> Result<DeepLUsage, translator::Error> {
+    // {"character_count": 180118, "character_limit": 1250000}
+    let count = |key: &str| value.get(key).and_then(Value::as_u64);
+    match (count("character_count"), count("character_limit")) {
+        (Some(character_count), Some(character_limit)) => Ok(DeepLUsage {
+            character_count,
+            character_limit,
+        }),
+        _ => Err(translator::Error::new(
+            ErrorKind::DecodeBody,
+            value.to_string(),
+        )),
+    }
+}
+
+fn get_languages(value: &Value) -> Result<Vec<Language>, translator::Error> {
+    // [{"language": "DE", "name": "German", "supports_formality": true}, ...]
+    let languages = value
+        .as_array()
+        .ok_or_else(|| translator::Error::new(ErrorKind::DecodeBody, value.to_string()))?;
+
+    Ok(languages
+        .iter()
+        .filter_map(|language| {
+            Some(Language {
+                code: language.get("language")?.as_str()?.to_string(),
+                name: language.get("name")?.as_str()?.to_string(),
+            })
+        })
+        .collect())
+}
@@ -0,0 +1,450 @@
+use crate::translators::chunks::{batches, Chunking};
+use crate::translators::deepl::languages;
+#[cfg(feature = "async")]
+use crate::translators::deepl::requests::{
+    send_async_languages_request, send_async_request, send_async_usage_request,
+};
+use crate::translators::deepl::requests::{
+    send_sync_languages_request, send_sync_request, send_sync_usage_request, RequestConfig,
+};
+use crate::translators::registry::BackendConfig;
+#[cfg(feature = "async")]
+use crate::translators::runtime;
+use crate::translators::translator::shared::{
+    self, chunking, connection, read_shared_config, validate_target,
+};
+use crate::translators::translator::{self, ErrorKind, Language, Translator};
+use crate::translators::transport::{self, TlsConfig, Transport};
+
+use macon::Builder;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+/// [DeepL API](https://developers.deepl.com), free or pro.
+///
+/// # Dependencies:
+/// Add to your dependency:
+/// ```no_run ignore
+/// [dependencies]
+/// translators = { version = "0.1.5", features = ["deepl", "tokio-async"] }
+/// ```
+/// # Examples
+///
+/// ```no_run ignore
+/// use translators::{DeepLTranslator, Formality, Translator};
+///
+/// fn main() {
+///     let deepl_trans = DeepLTranslator::builder()
+///         // free keys end with ":fx" and use api-free.deepl.com
+///         .api_key("xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx:fx")
+///         .formality(Formality::Less)
+///         .build();
+///     let translated_text = deepl_trans
+///         .translate_sync("Hello, world!", "", "de")
+///         .unwrap();
+///     println!("{}", translated_text);
+///
+///     // several texts with a single request
+///     let translated_texts = deepl_trans
+///         .translate_batch_sync(&["Hello", "Goodbye"], "en", "fr")
+///         .unwrap();
+///
+///     let usage = deepl_trans.usage_sync().unwrap();
+///     println!("{} chars left", usage.remaining());
+/// }
+/// ```
+#[derive(Builder, Clone, Debug)]
+#[builder(mode=Panic, Default)]
+pub struct DeepLTranslator {
+    /// Authentication key of the account.
+    pub api_key: String,
+    /// Url of the API, by default `https://api-free.deepl.com` for the free keys
+    /// (ending with `:fx`) and `https://api.deepl.com` for the pro ones.
+    pub url: Option<String>,
+    /// Formal or informal language, for the target languages that support it.
+    pub formality: Option<Formality>,
+    /// Glossary used for the translations, it needs a source language: the
+    /// translations without one fail with [`ErrorKind::Builder`].
+    pub glossary_id: Option<String>,
+    /// Translates the text of XML or HTML documents and keeps the markup.
+    ///
+    /// Set a `text_limit` larger than the documents, a chunk can end inside a tag.
+    pub tag_handling: Option<TagHandling>,
+    /// How long to wait for each request, from connect to the end of the body.
+    pub request_timeout: Duration,
+    /// How long to wait for the connection to the server.
+    pub connect_timeout: Option<Duration>,
+    /// How long a whole `translate_*` call can take, all chunks included.
+    pub total_timeout: Option<Duration>,
+    /// Delay between requests.
+    pub request_delay: Duration,
+    /// Proxy address, see [`crate::GoogleTranslator`] for the format.
+    pub proxy_address: Option<String>,
+    /// How many requests can be handled concurrently.
+    ///
+    /// Unlimited in async by default. The sync API translates the chunks on up to
+    /// `max_concurrency` threads, one at a time if unset.
+    pub max_concurrency: Option<usize>,
+    /// Maximum number of chars of a request, DeepL accepts bodies up to 128 KiB.
+    pub text_limit: usize,
+    /// Headers added to every request.
+    pub headers: Vec<(String, String)>,
+    /// Root certificates and client identity, e.g. for a TLS-inspecting proxy.
+    pub tls: Option<Arc<TlsConfig>>,
+    /// Sends the requests, reqwest by default (ureq without the `reqwest` feature).
+    #[builder(Into=!)]
+    pub transport: Arc<dyn Transport>,
+}
+
+/// Formality of the translation, see [`DeepLTranslator::formality`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Formality {
+    Default,
+    More,
+    Less,
+    /// Formal if the target language supports it, no error otherwise.
+    PreferMore,
+    /// Informal if the target language supports it, no error otherwise.
+    PreferLess,
+}
+
+impl Formality {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Formality::Default => "default",
+            Formality::More => "more",
+            Formality::Less => "less",
+            Formality::PreferMore => "prefer_more",
+            Formality::PreferLess => "prefer_less",
+        }
+    }
+}
+
+impl FromStr for Formality {
+    type Err = String;
+
+    /// Values of the DeepL API: `"default"`, `"more"`, `"less"`, `"prefer_more"`, `"prefer_less"`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "default" => Ok(Formality::Default),
+            "more" => Ok(Formality::More),
+            "less" => Ok(Formality::Less),
+            "prefer_more" => Ok(Formality::PreferMore),
+            "prefer_less" => Ok(Formality::PreferLess),
+            _ => Err(format!("unknown formality {s:?}")),
+        }
+    }
+}
+
+/// Markup of the texts, see [`DeepLTranslator::tag_handling`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TagHandling {
+    Xml,
+    Html,
+}
+
+impl TagHandling {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TagHandling::Xml => "xml",
+            TagHandling::Html => "html",
+        }
+    }
+}
+
+impl FromStr for TagHandling {
+    type Err = String;
+
+    /// `"xml"` or `"html"`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "xml" => Ok(TagHandling::Xml),
+            "html" => Ok(TagHandling::Html),
+            _ => Err(format!("unknown tag handling {s:?}")),
+        }
+    }
+}
+
+/// Characters translated in the current billing period.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeepLUsage {
+    pub character_count: u64,
+    pub character_limit: u64,
+}
+
+impl DeepLUsage {
+    /// Characters left before [`ErrorKind::QuotaExceeded`].
+    pub fn remaining(&self) -> u64 {
+        self.character_limit.saturating_sub(self.character_count)
+    }
+}
+
+impl translator::Translator for DeepLTranslator {
+    #[cfg(feature = "async")]
+    async fn translate_async(
+        &self,
+        text: &str,
+        source_language: &str,
+        target_language: &str,
+    ) -> Result<String, translator::Error> {
+        self.validate_languages(source_language, target_language)?;
+        let config = self.request_config();
+        self.chunking()
+            .translate_async(text, |chunk| async move {
+                let mut translated =
+                    send_async_request(target_language, source_language, &[chunk], &config).await?;
+                Ok(translated.remove(0))
+            })
+            .await
+    }
+
+    fn translate_sync(
+        &self,
+        text: &str,
+        source_language: &str,
+        target_language: &str,
+    ) -> Result<String, translator::Error> {
+        self.validate_languages(source_language, target_language)?;
+        self.chunking().translate_sync(text, |chunk, time_left| {
+            let mut config = self.request_config();
+            if let Some(time_left) = time_left {
+                config.connection.timeout = config.connection.timeout.min(time_left);
+            }
+            let mut translated =
+                send_sync_request(target_language, source_language, &[chunk], &config)?;
+            Ok(translated.remove(0))
+        })
+    }
+
+    /// Built-in list of the DeepL target languages.
+    ///
+    /// Use [`DeepLTranslator::fetch_languages_sync`] to get the current list.
+    fn supported_languages(&self) -> Vec<Language> {
+        shared::languages(languages::LANGUAGES)
+    }
+}
+
+impl DeepLTranslator {
+    /// Translates several texts, with as few requests as possible.
+    ///
+    /// The texts are sent by groups of up to 50 and `text_limit` chars. A text longer
+    /// than `text_limit` is split in chunks like with [`Translator::translate_sync`].
+    ///
+    /// The error of a group has its index as chunk index and the indexes of its texts
+    /// as chunk range.
+    pub fn translate_batch_sync(
+        &self,
+        texts: &[&str],
+        source_language: &str,
+        target_language: &str,
+    ) -> Result<Vec<String>, translator::Error> {
+        self.validate_languages(source_language, target_language)?;
+        let mut results = Vec::with_capacity(texts.len());
+        for (index, batch) in batches(texts, MAX_BATCH_TEXTS, self.text_limit)
+            .into_iter()
+            .enumerate()
+        {
+            if index > 0 && !self.request_delay.is_zero() {
+                thread::sleep(self.request_delay);
+            }
+            let batch_texts = &texts[batch.clone()];
+            if self.is_long(batch_texts) {
+                results.push(self.translate_sync(
+                    batch_texts[0],
+                    source_language,
+                    target_language,
+                )?);
+                continue;
+            }
+            let translated = send_sync_request(
+                target_language,
+                source_language,
+                batch_texts,
+                &self.request_config(),
+            )
+            .map_err(|e| e.with_backend(BACKEND).with_chunk(index, batch))?;
+            results.extend(translated);
+        }
+
+        Ok(results)
+    }
+
+    /// Translates several texts, with as few requests as possible.
+    ///
+    /// See [`DeepLTranslator::translate_batch_sync`].
+    #[cfg(feature = "async")]
+    pub async fn translate_batch_async(
+        &self,
+        texts: &[&str],
+        source_language: &str,
+        target_language: &str,
+    ) -> Result<Vec<String>, translator::Error> {
+        self.validate_languages(source_language, target_language)?;
+        let mut results = Vec::with_capacity(texts.len());
+        for (index, batch) in batches(texts, MAX_BATCH_TEXTS, self.text_limit)
+            .into_iter()
+            .enumerate()
+        {
+            if index > 0 && !self.request_delay.is_zero() {
+                runtime::sleep(self.request_delay).await;
+            }
+            let batch_texts = &texts[batch.clone()];
+            if self.is_long(batch_texts) {
+                results.push(
+                    self.translate_async(batch_texts[0], source_language, target_language)
+                        .await?,
+                );
+                continue;
+            }
+            let translated = send_async_request(
+                target_language,
+                source_language,
+                batch_texts,
+                &self.request_config(),
+            )
+            .await
+            .map_err(|e| e.with_backend(BACKEND).with_chunk(index, batch))?;
+            results.extend(translated);
+        }
+
+        Ok(results)
+    }
+
+    /// Characters translated in the current billing period and the limit of the account.
+    #[cfg(feature = "async")]
+    pub async fn usage_async(&self) -> Result<DeepLUsage, translator::Error> {
+        send_async_usage_request(&self.request_config())
+            .await
+            .map_err(|e| e.with_backend(BACKEND))
+    }
+
+    /// Characters translated in the current billing period and the limit of the account.
+    pub fn usage_sync(&self) -> Result<DeepLUsage, translator::Error> {
+        send_sync_usage_request(&self.request_config()).map_err(|e| e.with_backend(BACKEND))
+    }
+
+    /// Fetches the current list of target languages.
+    #[cfg(feature = "async")]
+    pub async fn fetch_languages_async(&self) -> Result<Vec<Language>, translator::Error> {
+        send_async_languages_request(&self.request_config())
+            .await
+            .map_err(|e| e.with_backend(BACKEND))
+    }
+
+    /// Fetches the current list of target languages.
+    pub fn fetch_languages_sync(&self) -> Result<Vec<Language>, translator::Error> {
+        send_sync_languages_request(&self.request_config()).map_err(|e| e.with_backend(BACKEND))
+    }
+
+    /// Builds a translator from string settings, missing keys keep the default values.
+    ///
+    /// Keys are the field names: `api_key`, `url`, `formality`, `glossary_id`,
+    /// `tag_handling`, `request_timeout`, `connect_timeout`, `total_timeout`,
+    /// `request_delay` (in seconds), `proxy_address`, `max_concurrency` and `text_limit`.
+    pub fn from_config(config: &BackendConfig) -> Result<Self, translator::Error> {
+        let mut translator = DeepLTranslator::default();
+        match config.get("api_key") {
+            Some(api_key) => translator.api_key = api_key.to_string(),
+            None => {
+                return Err(translator::Error::new(
+                    ErrorKind::Builder,
+                    "missing api_key for deepl",
+                ))
+            }
+        }
+        if let Some(url) = config.get("url") {
+            translator.url = Some(url.to_string());
+        }
+        if let Some(formality) = config.parse("formality")? {
+            translator.formality = Some(formality);
+        }
+        if let Some(glossary_id) = config.get("glossary_id") {
+            translator.glossary_id = Some(glossary_id.to_string());
+        }
+        if let Some(tag_handling) = config.parse("tag_handling")? {
+            translator.tag_handling = Some(tag_handling);
+        }
+        read_shared_config!(translator, config);
+
+        Ok(translator)
+    }
+
+    /// Url of the API: the configured one, or the free or pro one depending on the key.
+    pub fn api_url(&self) -> &str {
+        match &self.url {
+            Some(url) => url,
+            None if self.api_key.ends_with(":fx") => FREE_URL,
+            None => PRO_URL,
+        }
+    }
+
+    /// The target can't be one of the codes of the detection, and a glossary needs a
+    /// source language.
+    fn validate_languages(
+        &self,
+        source_language: &str,
+        target_language: &str,
+    ) -> Result<(), translator::Error> {
+        validate_target(target_language, BACKEND)?;
+        if self.glossary_id.is_some() && matches!(source_language.trim(), "" | "auto") {
+            return Err(translator::Error::new(
+                ErrorKind::Builder,
+                "glossary_id needs a source language",
+            )
+            .with_backend(BACKEND));
+        }
+
+        Ok(())
+    }
+
+    /// A single text longer than `text_limit`, split in chunks.
+    fn is_long(&self, texts: &[&str]) -> bool {
+        matches!(texts, [text] if text.chars().count() > self.text_limit)
+    }
+
+    fn chunking(&self) -> Chunking {
+        chunking!(self, BACKEND)
+    }
+
+    fn request_config(&self) -> RequestConfig<'_> {
+        RequestConfig {
+            connection: connection!(self),
+            url: self.api_url(),
+            api_key: &self.api_key,
+            formality: self.formality,
+            glossary_id: self.glossary_id.as_deref(),
+            tag_handling: self.tag_handling,
+        }
+    }
+}
+
+impl Default for DeepLTranslator {
+    fn default() -> Self {
+        DeepLTranslator {
+            api_key: String::new(),
+            url: None,
+            formality: None,
+            glossary_id: None,
+            tag_handling: None,
+            request_timeout: Duration::from_secs(35),
+            connect_timeout: None,
+            total_timeout: None,
+            request_delay: Duration::ZERO,
+            proxy_address: None,
+            max_concurrency: None,
+            text_limit: 30000,
+            headers: Vec::new(),
+            tls: None,
+            transport: transport::default_transport(),
+        }
+    }
+}
+
+/// Name of the translator in the errors.
+const BACKEND: &str = "deepl";
+const FREE_URL: &str = "https://api-free.deepl.com";
+const PRO_URL: &str = "https://api.deepl.com";
+/// Maximum number of texts of a request.
+const MAX_BATCH_TEXTS: usize = 50;
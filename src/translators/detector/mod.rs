@@ -0,0 +1,24 @@
+#[cfg(feature = "offline-detect")]
+pub(crate) mod offline;
+
+use crate::translators::translator::Error;
+use std::fmt::Debug;
+#[cfg(feature = "async")]
+use std::future::Future;
+
+/// Detects the language of a text without translating it.
+pub trait LanguageDetector: Debug + Send + Sync {
+    #[cfg(feature = "async")]
+    fn detect_async(&self, text: &str) -> impl Future<Output = Result<Detection, Error>> + Send;
+
+    fn detect_sync(&self, text: &str) -> Result<Detection, Error>;
+}
+
+/// Result of a language detection.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Detection {
+    /// Language code in the format used by Google Translate, e.g. `"en"` or `"zh-CN"`.
+    pub language: String,
+    /// How sure the detector is, from `0.0` to `1.0`.
+    pub confidence: f64,
+}
@@ -0,0 +1,116 @@
+use crate::translators::detector::{Detection, LanguageDetector};
+use crate::translators::translator::{Error, ErrorKind};
+#[cfg(feature = "async")]
+use std::future::Future;
+use whatlang::Lang;
+
+/// Offline language detector, no network requests are made.
+///
+/// Based on [whatlang](https://docs.rs/whatlang), supports about 70 languages.
+/// Useful to skip the network call when the language is obvious:
+///
+/// ``` ignore
+/// use translators::{LanguageDetector, OfflineDetector};
+///
+/// let detection = OfflineDetector::default()
+///     .detect_sync("Ceci est un texte en français")
+///     .unwrap();
+/// assert_eq!(detection.language, "fr");
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct OfflineDetector;
+
+impl LanguageDetector for OfflineDetector {
+    #[cfg(feature = "async")]
+    fn detect_async(&self, text: &str) -> impl Future<Output = Result<Detection, Error>> + Send {
+        std::future::ready(self.detect_sync(text))
+    }
+
+    fn detect_sync(&self, text: &str) -> Result<Detection, Error> {
+        match whatlang::detect(text) {
+            Some(info) => Ok(Detection {
+                language: language_code(info.lang()).to_string(),
+                confidence: info.confidence(),
+            }),
+            None => Err(Error::new(
+                ErrorKind::LanguageDetection,
+                "could not detect the language",
+            )),
+        }
+    }
+}
+
+// ISO 639-3 (whatlang) to google-translate codes
+fn language_code(lang: Lang) -> &'static str {
+    match lang {
+        Lang::Epo => "eo",
+        Lang::Eng => "en",
+        Lang::Rus => "ru",
+        Lang::Cmn => "zh-CN",
+        Lang::Spa => "es",
+        Lang::Por => "pt",
+        Lang::Ita => "it",
+        Lang::Ben => "bn",
+        Lang::Fra => "fr",
+        Lang::Deu => "de",
+        Lang::Ukr => "uk",
+        Lang::Kat => "ka",
+        Lang::Ara => "ar",
+        Lang::Hin => "hi",
+        Lang::Jpn => "ja",
+        Lang::Heb => "iw",
+        Lang::Yid => "yi",
+        Lang::Pol => "pl",
+        Lang::Amh => "am",
+        Lang::Jav => "jw",
+        Lang::Kor => "ko",
+        Lang::Nob => "no",
+        Lang::Dan => "da",
+        Lang::Swe => "sv",
+        Lang::Fin => "fi",
+        Lang::Tur => "tr",
+        Lang::Nld => "nl",
+        Lang::Hun => "hu",
+        Lang::Ces => "cs",
+        Lang::Ell => "el",
+        Lang::Bul => "bg",
+        Lang::Bel => "be",
+        Lang::Mar => "mr",
+        Lang::Kan => "kn",
+        Lang::Ron => "ro",
+        Lang::Slv => "sl",
+        Lang::Hrv => "hr",
+        Lang::Srp => "sr",
+        Lang::Mkd => "mk",
+        Lang::Lit => "lt",
+        Lang::Lav => "lv",
+        Lang::Est => "et",
+        Lang::Tam => "ta",
+        Lang::Vie => "vi",
+        Lang::Urd => "ur",
+        Lang::Tha => "th",
+        Lang::Guj => "gu",
+        Lang::Uzb => "uz",
+        Lang::Pan => "pa",
+        Lang::Aze => "az",
+        Lang::Ind => "id",
+        Lang::Tel => "te",
+        Lang::Pes => "fa",
+        Lang::Mal => "ml",
+        Lang::Ori => "or",
+        Lang::Mya => "my",
+        Lang::Nep => "ne",
+        Lang::Sin => "si",
+        Lang::Khm => "km",
+        Lang::Tuk => "tk",
+        Lang::Aka => "ak",
+        Lang::Zul => "zu",
+        Lang::Sna => "sn",
+        Lang::Afr => "af",
+        Lang::Lat => "la",
+        Lang::Slk => "sk",
+        Lang::Cat => "ca",
+        Lang::Tgl => "tl",
+        Lang::Hye => "hy",
+    }
+}
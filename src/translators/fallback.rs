@@ -0,0 +1,339 @@
+use crate::translators::chunks::split_chunks;
+use crate::translators::circuit::{CircuitBreaker, CircuitPermit};
+use crate::translators::translator::{self, DynTranslator, Error, ErrorKind, Language};
+
+#[cfg(feature = "async")]
+use futures::stream::{self, StreamExt};
+use std::collections::HashSet;
+use std::ops::Range;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Translators tried in order for each chunk of a text, e.g. Google then Bing.
+///
+/// A chunk goes to the next translator when one fails with a retryable error, a used up
+/// quota or an unsupported language. A translator failing several times in a row is
+/// skipped for a while by its [`CircuitBreaker`], then tried again with a single chunk.
+///
+/// ```ignore
+/// use translators::{BingTranslator, FallbackTranslator, GoogleTranslator, Translator};
+///
+/// let translator = FallbackTranslator::new()
+///     .with_circuit_breaker(3, Duration::from_secs(300))
+///     .backend("google", GoogleTranslator::default())
+///     .backend("bing", BingTranslator::default());
+/// let translation = translator
+///     .translate_detailed_sync("Hello, world!", "en", "fr")
+///     .unwrap();
+/// println!("{} by {}", translation.text, translation.chunks[0].backend);
+/// ```
+#[derive(Clone, Debug)]
+pub struct FallbackTranslator {
+    backends: Vec<FallbackBackend>,
+    text_limit: usize,
+    max_concurrency: Option<usize>,
+    failure_threshold: u32,
+    cooldown: Duration,
+}
+
+/// Translator of a [`FallbackTranslator`].
+#[derive(Clone, Debug)]
+pub struct FallbackBackend {
+    name: String,
+    translator: Arc<dyn DynTranslator>,
+    breaker: CircuitBreaker,
+}
+
+/// Translated text with the translator of each chunk.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FallbackTranslation {
+    pub text: String,
+    pub chunks: Vec<ServedChunk>,
+}
+
+/// Chunk of a [`FallbackTranslation`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServedChunk {
+    /// Byte range of the chunk in the original text.
+    pub range: Range<usize>,
+    /// Name of the translator of the chunk.
+    pub backend: String,
+}
+
+impl FallbackTranslator {
+    /// Chain without translators, chunks of 5000 chars, circuits opening after
+    /// 5 failures for 1 minute.
+    pub fn new() -> Self {
+        FallbackTranslator {
+            backends: Vec::new(),
+            text_limit: 5000,
+            max_concurrency: None,
+            failure_threshold: 5,
+            cooldown: Duration::from_secs(60),
+        }
+    }
+
+    /// Adds a translator at the end of the chain.
+    pub fn backend(
+        self,
+        name: impl Into<String>,
+        translator: impl DynTranslator + 'static,
+    ) -> Self {
+        let translator: Arc<dyn DynTranslator> = Arc::new(translator);
+        self.dyn_backend(name, translator)
+    }
+
+    /// Adds a translator at the end of the chain, e.g. one of a
+    /// [`crate::TranslatorRegistry`].
+    pub fn dyn_backend(
+        mut self,
+        name: impl Into<String>,
+        translator: impl Into<Arc<dyn DynTranslator>>,
+    ) -> Self {
+        self.backends.push(FallbackBackend {
+            name: name.into(),
+            translator: translator.into(),
+            breaker: CircuitBreaker::new(self.failure_threshold, self.cooldown),
+        });
+        self
+    }
+
+    /// Maximum number of chars of a chunk, the translators can split it again.
+    pub fn with_text_limit(mut self, text_limit: usize) -> Self {
+        self.text_limit = text_limit;
+        self
+    }
+
+    /// How many chunks are translated at once by the async API, all of them if unset.
+    ///
+    /// The sync API translates them one at a time.
+    pub fn with_max_concurrency(mut self, max_concurrency: usize) -> Self {
+        self.max_concurrency = Some(max_concurrency.max(1));
+        self
+    }
+
+    /// Settings of the circuit breakers, the ones of the translators already added are
+    /// replaced.
+    pub fn with_circuit_breaker(mut self, failure_threshold: u32, cooldown: Duration) -> Self {
+        self.failure_threshold = failure_threshold;
+        self.cooldown = cooldown;
+        for backend in &mut self.backends {
+            backend.breaker = CircuitBreaker::new(failure_threshold, cooldown);
+        }
+        self
+    }
+
+    /// The translators in order, with the state of their circuit.
+    pub fn backends(&self) -> &[FallbackBackend] {
+        &self.backends
+    }
+
+    /// Translates the text and tells which translator served each chunk.
+    pub fn translate_detailed_sync(
+        &self,
+        text: &str,
+        source_language: &str,
+        target_language: &str,
+    ) -> Result<FallbackTranslation, Error> {
+        let mut translation = FallbackTranslation::default();
+        for (index, chunk) in split_chunks(text, self.text_limit).iter().enumerate() {
+            let (translated_chunk, backend) = self
+                .translate_chunk_sync(&text[chunk.range()], source_language, target_language)
+                .map_err(|e| e.with_chunk(index, chunk.range()))?;
+            translation.text.push_str(&translated_chunk);
+            translation.chunks.push(ServedChunk {
+                range: chunk.range(),
+                backend,
+            });
+        }
+
+        Ok(translation)
+    }
+
+    /// Translates the text and tells which translator served each chunk.
+    #[cfg(feature = "async")]
+    pub async fn translate_detailed_async(
+        &self,
+        text: &str,
+        source_language: &str,
+        target_language: &str,
+    ) -> Result<FallbackTranslation, Error> {
+        let chunks = split_chunks(text, self.text_limit);
+        let max_concurrency = self.max_concurrency.unwrap_or(chunks.len()).max(1);
+        // up to `max_concurrency` chunks at once, the results come in order
+        let tasks: Vec<_> = chunks
+            .iter()
+            .enumerate()
+            .map(|(index, chunk)| async move {
+                self.translate_chunk_async(&text[chunk.range()], source_language, target_language)
+                    .await
+                    .map_err(|e| e.with_chunk(index, chunk.range()))
+            })
+            .collect();
+        let mut translated_chunks = stream::iter(tasks).buffered(max_concurrency);
+        let mut translation = FallbackTranslation::default();
+        for chunk in &chunks {
+            let (translated_chunk, backend) = translated_chunks
+                .next()
+                .await
+                .expect("a translation for each chunk")?;
+            translation.text.push_str(&translated_chunk);
+            translation.chunks.push(ServedChunk {
+                range: chunk.range(),
+                backend,
+            });
+        }
+
+        Ok(translation)
+    }
+
+    fn translate_chunk_sync(
+        &self,
+        chunk: &str,
+        source_language: &str,
+        target_language: &str,
+    ) -> Result<(String, String), Error> {
+        let mut last_error = None;
+        for backend in &self.backends {
+            let Some(permit) = backend.breaker.try_acquire() else {
+                continue;
+            };
+            let result =
+                backend
+                    .translator
+                    .translate_sync_dyn(chunk, source_language, target_language);
+            match record(result, &permit) {
+                Ok(translated_chunk) => return Ok((translated_chunk, backend.name.clone())),
+                Err(e) if falls_back(&e) => last_error = Some(e),
+                Err(e) => return Err(e),
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| self.no_backend_error()))
+    }
+
+    #[cfg(feature = "async")]
+    async fn translate_chunk_async(
+        &self,
+        chunk: &str,
+        source_language: &str,
+        target_language: &str,
+    ) -> Result<(String, String), Error> {
+        let mut last_error = None;
+        for backend in &self.backends {
+            let Some(permit) = backend.breaker.try_acquire() else {
+                continue;
+            };
+            let result = backend
+                .translator
+                .translate_async_dyn(chunk, source_language, target_language)
+                .await;
+            match record(result, &permit) {
+                Ok(translated_chunk) => return Ok((translated_chunk, backend.name.clone())),
+                Err(e) if falls_back(&e) => last_error = Some(e),
+                Err(e) => return Err(e),
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| self.no_backend_error()))
+    }
+
+    /// No translator, or all of them are skipped by their circuit.
+    fn no_backend_error(&self) -> Error {
+        if self.backends.is_empty() {
+            return Error::new(ErrorKind::Builder, "no translator in the fallback chain")
+                .with_backend(BACKEND);
+        }
+        let retry_after = self
+            .backends
+            .iter()
+            .filter_map(|backend| backend.breaker.retry_after())
+            .min();
+        Error::new(
+            ErrorKind::CircuitOpen { retry_after },
+            "the circuits of all the translators are open",
+        )
+        .with_backend(BACKEND)
+    }
+}
+
+impl FallbackBackend {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn translator(&self) -> &Arc<dyn DynTranslator> {
+        &self.translator
+    }
+
+    /// Circuit breaker of the translator, to see or reset its state.
+    pub fn breaker(&self) -> &CircuitBreaker {
+        &self.breaker
+    }
+}
+
+/// Updates the circuit with the result of a request: any answer of the service
+/// closes it, even an error.
+fn record(result: Result<String, Error>, permit: &CircuitPermit) -> Result<String, Error> {
+    match &result {
+        Err(e) if is_failure(e) => permit.record_failure(),
+        Ok(_) => permit.record_success(),
+        Err(e) if e.status().is_some() => permit.record_success(),
+        // a local error, e.g. an unsupported language, says nothing about the service
+        Err(_) => {}
+    }
+    result
+}
+
+/// The service is unreachable, blocks the requests or has no quota left.
+fn is_failure(e: &Error) -> bool {
+    e.is_retryable() || *e.kind() == ErrorKind::QuotaExceeded
+}
+
+/// Another translator can translate the chunk.
+fn falls_back(e: &Error) -> bool {
+    is_failure(e) || *e.kind() == ErrorKind::UnsupportedLanguage
+}
+
+impl translator::Translator for FallbackTranslator {
+    #[cfg(feature = "async")]
+    async fn translate_async(
+        &self,
+        text: &str,
+        source_language: &str,
+        target_language: &str,
+    ) -> Result<String, Error> {
+        self.translate_detailed_async(text, source_language, target_language)
+            .await
+            .map(|translation| translation.text)
+    }
+
+    fn translate_sync(
+        &self,
+        text: &str,
+        source_language: &str,
+        target_language: &str,
+    ) -> Result<String, Error> {
+        self.translate_detailed_sync(text, source_language, target_language)
+            .map(|translation| translation.text)
+    }
+
+    /// Languages of at least one of the translators.
+    fn supported_languages(&self) -> Vec<Language> {
+        let mut codes = HashSet::new();
+        self.backends
+            .iter()
+            .flat_map(|backend| backend.translator.supported_languages_dyn())
+            .filter(|language| codes.insert(language.code.clone()))
+            .collect()
+    }
+}
+
+impl Default for FallbackTranslator {
+    fn default() -> Self {
+        FallbackTranslator::new()
+    }
+}
+
+/// Name of the translator in the errors.
+const BACKEND: &str = "fallback";
@@ -0,0 +1,228 @@
+use crate::translators::translator::{self, ErrorKind, Language};
+use html_escape::decode_html_entities;
+use regex::Regex;
+
+/// Languages supported by Google Translate, `(code, name)`.
+pub(crate) const LANGUAGES: &[(&str, &str)] = &[
+    ("af", "Afrikaans"),
+    ("sq", "Albanian"),
+    ("am", "Amharic"),
+    ("ar", "Arabic"),
+    ("hy", "Armenian"),
+    ("as", "Assamese"),
+    ("ay", "Aymara"),
+    ("az", "Azerbaijani"),
+    ("bm", "Bambara"),
+    ("eu", "Basque"),
+    ("be", "Belarusian"),
+    ("bn", "Bengali"),
+    ("bho", "Bhojpuri"),
+    ("bs", "Bosnian"),
+    ("bg", "Bulgarian"),
+    ("ca", "Catalan"),
+    ("ceb", "Cebuano"),
+    ("ny", "Chichewa"),
+    ("zh-CN", "Chinese (Simplified)"),
+    ("zh-TW", "Chinese (Traditional)"),
+    ("co", "Corsican"),
+    ("hr", "Croatian"),
+    ("cs", "Czech"),
+    ("da", "Danish"),
+    ("dv", "Dhivehi"),
+    ("doi", "Dogri"),
+    ("nl", "Dutch"),
+    ("en", "English"),
+    ("eo", "Esperanto"),
+    ("et", "Estonian"),
+    ("ee", "Ewe"),
+    ("tl", "Filipino"),
+    ("fi", "Finnish"),
+    ("fr", "French"),
+    ("fy", "Frisian"),
+    ("gl", "Galician"),
+    ("ka", "Georgian"),
+    ("de", "German"),
+    ("el", "Greek"),
+    ("gn", "Guarani"),
+    ("gu", "Gujarati"),
+    ("ht", "Haitian Creole"),
+    ("ha", "Hausa"),
+    ("haw", "Hawaiian"),
+    ("iw", "Hebrew"),
+    ("hi", "Hindi"),
+    ("hmn", "Hmong"),
+    ("hu", "Hungarian"),
+    ("is", "Icelandic"),
+    ("ig", "Igbo"),
+    ("ilo", "Ilocano"),
+    ("id", "Indonesian"),
+    ("ga", "Irish"),
+    ("it", "Italian"),
+    ("ja", "Japanese"),
+    ("jw", "Javanese"),
+    ("kn", "Kannada"),
+    ("kk", "Kazakh"),
+    ("km", "Khmer"),
+    ("rw", "Kinyarwanda"),
+    ("gom", "Konkani"),
+    ("ko", "Korean"),
+    ("kri", "Krio"),
+    ("ku", "Kurdish (Kurmanji)"),
+    ("ckb", "Kurdish (Sorani)"),
+    ("ky", "Kyrgyz"),
+    ("lo", "Lao"),
+    ("la", "Latin"),
+    ("lv", "Latvian"),
+    ("ln", "Lingala"),
+    ("lt", "Lithuanian"),
+    ("lg", "Luganda"),
+    ("lb", "Luxembourgish"),
+    ("mk", "Macedonian"),
+    ("mai", "Maithili"),
+    ("mg", "Malagasy"),
+    ("ms", "Malay"),
+    ("ml", "Malayalam"),
+    ("mt", "Maltese"),
+    ("mi", "Maori"),
+    ("mr", "Marathi"),
+    ("mni-Mtei", "Meiteilon (Manipuri)"),
+    ("lus", "Mizo"),
+    ("mn", "Mongolian"),
+    ("my", "Myanmar (Burmese)"),
+    ("ne", "Nepali"),
+    ("no", "Norwegian"),
+    ("or", "Odia (Oriya)"),
+    ("om", "Oromo"),
+    ("ps", "Pashto"),
+    ("fa", "Persian"),
+    ("pl", "Polish"),
+    ("pt", "Portuguese"),
+    ("pa", "Punjabi"),
+    ("qu", "Quechua"),
+    ("ro", "Romanian"),
+    ("ru", "Russian"),
+    ("sm", "Samoan"),
+    ("sa", "Sanskrit"),
+    ("gd", "Scots Gaelic"),
+    ("nso", "Sepedi"),
+    ("sr", "Serbian"),
+    ("st", "Sesotho"),
+    ("sn", "Shona"),
+    ("sd", "Sindhi"),
+    ("si", "Sinhala"),
+    ("sk", "Slovak"),
+    ("sl", "Slovenian"),
+    ("so", "Somali"),
+    ("es", "Spanish"),
+    ("su", "Sundanese"),
+    ("sw", "Swahili"),
+    ("sv", "Swedish"),
+    ("tg", "Tajik"),
+    ("ta", "Tamil"),
+    ("tt", "Tatar"),
+    ("te", "Telugu"),
+    ("th", "Thai"),
+    ("ti", "Tigrinya"),
+    ("ts", "Tsonga"),
+    ("tr", "Turkish"),
+    ("tk", "Turkmen"),
+    ("ak", "Twi"),
+    ("uk", "Ukrainian"),
+    ("ur", "Urdu"),
+    ("ug", "Uyghur"),
+    ("uz", "Uzbek"),
+    ("vi", "Vietnamese"),
+    ("cy", "Welsh"),
+    ("xh", "Xhosa"),
+    ("yi", "Yiddish"),
+    ("yo", "Yoruba"),
+    ("zu", "Zulu"),
+];
+
+/// Codes accepted by Google Translate besides the ones from [`LANGUAGES`], `(alias, code)`.
+const ALIASES: &[(&str, &str)] = &[("he", "iw"), ("jv", "jw"), ("zh", "zh-CN"), ("fil", "tl")];
+
+/// Checks the codes before sending a request, `""` and `"auto"` are only valid as source.
+///
+/// With `strict` the codes must be in [`LANGUAGES`], otherwise they only have to look like
+/// language tags.
+pub(crate) fn validate_languages(
+    source_language: &str,
+    target_language: &str,
+    strict: bool,
+) -> Result<(), translator::Error> {
+    let is_valid = |code| {
+        if strict {
+            is_supported(code)
+        } else {
+            is_language_tag(code)
+        }
+    };
+    if !matches!(source_language, "" | "auto") && !is_valid(source_language) {
+        return Err(translator::Error::new(
+            ErrorKind::UnsupportedLanguage,
+            source_language,
+        ));
+    }
+    if !is_valid(target_language) {
+        return Err(translator::Error::new(
+            ErrorKind::UnsupportedLanguage,
+            target_language,
+        ));
+    }
+
+    Ok(())
+}
+
+/// Whether two codes mean the same language, e.g. `"he"` and `"iw"`.
+pub(crate) fn same_language(first: &str, second: &str) -> bool {
+    canonical_code(first).eq_ignore_ascii_case(canonical_code(second))
+}
+
+fn canonical_code(code: &str) -> &str {
+    ALIASES
+        .iter()
+        .find(|(alias, _)| alias.eq_ignore_ascii_case(code))
+        .map_or(code, |(_, canonical)| canonical)
+}
+
+fn is_supported(code: &str) -> bool {
+    let code = canonical_code(code);
+    LANGUAGES
+        .iter()
+        .any(|(supported, _)| supported.eq_ignore_ascii_case(code))
+}
+
+/// A language and its subtags, like `"fr"`, `"yue"`, `"zh-CN"` or `"sr-Latn"`.
+fn is_language_tag(code: &str) -> bool {
+    let mut subtags = code.split('-');
+    let language = subtags.next().unwrap_or_default();
+    (2..=3).contains(&language.len())
+        && language.chars().all(|c| c.is_ascii_alphabetic())
+        && subtags.all(|subtag| {
+            (1..=8).contains(&subtag.len()) && subtag.chars().all(|c| c.is_ascii_alphanumeric())
+        })
+}
+
+pub(crate) fn get_languages(html: &str) -> Result<Vec<Language>, translator::Error> {
+    // <div class="language-item"><a href="./m?sl=auto&amp;tl=af&amp;hl=en">Afrikaans</a></div>
+    let pattern =
+        Regex::new(r#"class="language-item"><a href="[^"]*?[?;]tl=([^&"]+)[^"]*">([^<]+)<"#)
+            .unwrap();
+    let languages: Vec<Language> = pattern
+        .captures_iter(html)
+        .map(|captures| Language {
+            code: captures[1].to_string(),
+            name: decode_html_entities(&captures[2]).to_string(),
+        })
+        .collect();
+
+    if languages.is_empty() {
+        Err(translator::Error::new(
+            ErrorKind::DecodeBody,
+            "language list not found",
+        ))
+    } else {
+        Ok(languages)
+    }
+}
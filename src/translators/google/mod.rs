@@ -1,2 +1,3 @@
+pub(crate) mod languages;
 mod requests;
 pub(crate) mod translator;
@@ -1,85 +1,491 @@
-use crate::translators::translator;
+use crate::translators::circuit::{CircuitBreaker, CircuitPermit};
+use crate::translators::concurrency::{AdaptiveConcurrency, ConcurrencyPermit};
+use crate::translators::detector::Detection;
+use crate::translators::google::languages::get_languages;
+#[cfg(feature = "async")]
+#[cfg(feature = "async")]
+use crate::translators::hedging::Hedging;
+use crate::translators::rotation::RotationPool;
+use crate::translators::translator::shared;
+use crate::translators::translator::{self, ErrorKind, Language};
+use crate::translators::transport::{
+    CookieJar, IpVersion, Request, Response, TlsConfig, Transport,
+};
 use html_escape::decode_html_entities;
 use regex::Regex;
-use reqwest::blocking::Client as ClientSync;
-#[cfg(feature = "tokio-async")]
-use reqwest::Client as ClientAsync;
-use reqwest::Proxy;
+use serde_json::Value;
+use std::net::IpAddr;
+use std::sync::Arc;
+use std::time::Duration;
 use urlencoding::encode;
 
-#[cfg(feature = "tokio-async")]
+/// Domain of the translation and languages pages.
+const TRANSLATE_DOMAIN: &str = "translate.google.com";
+/// Domain of the JSON API, used for the detection.
+const API_DOMAIN: &str = "translate.googleapis.com";
+
+/// Settings of a single request.
+#[derive(Debug, Clone, Copy)]
+pub struct RequestConfig<'a> {
+    pub transport: &'a dyn Transport,
+    /// How long to wait for the whole request, from connect to the end of the body.
+    pub timeout: Duration,
+    /// How long to wait for the connection.
+    pub connect_timeout: Option<Duration>,
+    pub proxy_address: Option<&'a str>,
+    pub headers: &'a [(String, String)],
+    pub user_agents: Option<&'a RotationPool<String>>,
+    pub accept_language: Option<&'a str>,
+    pub cookie_jar: Option<&'a CookieJar>,
+    pub tls: Option<&'a Arc<TlsConfig>>,
+    pub local_addresses: Option<&'a RotationPool<IpAddr>>,
+    pub ip_version: Option<IpVersion>,
+    pub domains: Option<&'a RotationPool<String>>,
+    pub circuit_breaker: Option<&'a CircuitBreaker>,
+    pub adaptive_concurrency: Option<&'a AdaptiveConcurrency>,
+    /// Duplicates of the slow async requests.
+    #[cfg(feature = "async")]
+    pub hedging: Option<&'a Hedging>,
+}
+
+impl RequestConfig<'_> {
+    fn request(&self, url: &str) -> Request {
+        let mut request = Request {
+            headers: self.headers.to_vec(),
+            timeout: self.timeout,
+            connect_timeout: self.connect_timeout,
+            proxy_address: self.proxy_address.map(str::to_string),
+            tls: self.tls.cloned(),
+            local_address: self.local_addresses.and_then(RotationPool::next),
+            ip_version: self.ip_version,
+            ..Request::get(url)
+        };
+        if let Some(user_agent) = self.user_agents.and_then(RotationPool::next) {
+            request = request.header("User-Agent", user_agent);
+        }
+        if let Some(accept_language) = self.accept_language {
+            request = request.header("Accept-Language", accept_language);
+        }
+        if let Some(cookie) = self.cookie_jar.and_then(|jar| jar.header(url)) {
+            request = request.header("Cookie", cookie);
+        }
+
+        request
+    }
+
+    /// Next domain of the rotation, `default_domain` without domains.
+    fn domain(&self, default_domain: &str) -> String {
+        self.domains
+            .and_then(RotationPool::next)
+            .unwrap_or_else(|| default_domain.to_string())
+    }
+
+    /// Each domain is tried at most once per request.
+    fn domain_attempts(&self) -> usize {
+        self.domains
+            .map_or(1, |domains| domains.items().len())
+            .max(1)
+    }
+
+    /// Google blocked the domain or the local address of the request,
+    /// the next requests use other ones.
+    fn quarantine(&self, request: &Request, domain: &str, error: &translator::Error) {
+        if let (Some(domains), true) = (self.domains, is_blocked(error)) {
+            domains.quarantine(&domain.to_string());
+        }
+        if error.kind() != &ErrorKind::Captcha {
+            return;
+        }
+        if let (Some(local_addresses), Some(local_address)) =
+            (self.local_addresses, request.local_address)
+        {
+            local_addresses.quarantine(&local_address);
+        }
+    }
+
+    /// Refuses the request while the circuit is open.
+    ///
+    /// The permit of a cancelled probe lets the next request probe the circuit.
+    fn acquire(&self) -> Result<Option<CircuitPermit>, translator::Error> {
+        let Some(breaker) = self.circuit_breaker else {
+            return Ok(None);
+        };
+        match breaker.try_acquire() {
+            Some(permit) => Ok(Some(permit)),
+            None => Err(translator::Error::new(
+                ErrorKind::CircuitOpen {
+                    retry_after: breaker.retry_after(),
+                },
+                "too many captchas, 429 or connection failures",
+            )),
+        }
+    }
+
+    /// Any HTTP answer of Google closes the circuit, except a captcha or a 429. The local
+    /// errors (a client that can't be built, ...) say nothing about Google and are left out.
+    ///
+    /// The concurrency limit grows with the successes and is cut by a captcha, a 429
+    /// or a timeout.
+    fn record<T>(
+        &self,
+        result: &Result<T, translator::Error>,
+        permit: Option<&ConcurrencyPermit>,
+        circuit: Option<&CircuitPermit>,
+    ) {
+        if let Some(permit) = permit {
+            match result {
+                Ok(_) => permit.record_success(),
+                Err(e) if is_blocked(e) || e.kind() == &ErrorKind::Timeout => {
+                    permit.record_overload()
+                }
+                Err(_) => {}
+            }
+        }
+        if let Some(circuit) = circuit {
+            match result {
+                Err(e) if is_blocked(e) || is_unreachable(e) => circuit.record_failure(),
+                Ok(_) => circuit.record_success(),
+                Err(e) if e.status().is_some() => circuit.record_success(),
+                Err(_) => {}
+            }
+        }
+    }
+
+    fn store_cookies(&self, response: &Response) {
+        if let Some(cookie_jar) = self.cookie_jar {
+            for set_cookie in response.header_all("set-cookie") {
+                cookie_jar.store(&response.url, set_cookie);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "async")]
 pub async fn send_async_request(
     target_language: &str,
     source_language: &str,
     text: &str,
-    timeout: usize,
-    proxy_address: Option<&str>,
+    config: &RequestConfig<'_>,
 ) -> Result<String, translator::Error> {
-    // client build
-    let mut client = ClientAsync::builder();
-    // proxy
-    if let Some(proxy_address) = proxy_address {
-        let proxy = Proxy::all(proxy_address)?;
-        client = client.proxy(proxy);
-    }
-    let client = client.build()?;
-
-    // send req
-    let url = prepare_url(target_language, source_language, text);
-    let response = client
-        .get(&url)
-        .timeout(std::time::Duration::from_secs(timeout as u64))
-        .send()
-        .await?;
-
-    let result_html = response.text().await?;
+    let (domain, result) = get_async(config, TRANSLATE_DOMAIN, |domain| {
+        prepare_url(domain, target_language, source_language, text)
+    })
+    .await?;
 
     // look for translated text
-    get_translated_text(&result_html)
+    get_translated_text(&domain, &result)
 }
+
 pub fn send_sync_request(
     target_language: &str,
     source_language: &str,
     text: &str,
-    timeout: usize,
-    proxy_address: Option<&str>,
+    config: &RequestConfig<'_>,
 ) -> Result<String, translator::Error> {
-    // client build
-    let mut client = ClientSync::builder();
-    // proxy
-    if let Some(proxy_address) = proxy_address {
-        let proxy = Proxy::all(proxy_address)?;
-        client = client.proxy(proxy);
-    }
-    let client = client.build()?;
+    let (domain, result) = get_sync(config, TRANSLATE_DOMAIN, |domain| {
+        prepare_url(domain, target_language, source_language, text)
+    })?;
+
+    // look for translated text
+    get_translated_text(&domain, &result)
+}
+
+#[cfg(feature = "async")]
+pub async fn send_async_detect_request(
+    target_language: &str,
+    text: &str,
+    config: &RequestConfig<'_>,
+) -> Result<(String, Detection), translator::Error> {
+    let (_, result_json) = get_async(config, API_DOMAIN, |domain| {
+        prepare_api_url(domain, target_language, "auto", text)
+    })
+    .await?;
+
+    get_detection(&result_json)
+}
+
+pub fn send_sync_detect_request(
+    target_language: &str,
+    text: &str,
+    config: &RequestConfig<'_>,
+) -> Result<(String, Detection), translator::Error> {
+    let (_, result_json) = get_sync(config, API_DOMAIN, |domain| {
+        prepare_api_url(domain, target_language, "auto", text)
+    })?;
+
+    get_detection(&result_json)
+}
+
+#[cfg(feature = "async")]
+pub async fn send_async_languages_request(
+    display_language: &str,
+    config: &RequestConfig<'_>,
+) -> Result<Vec<Language>, translator::Error> {
+    let (_, result_html) = get_async(config, TRANSLATE_DOMAIN, |domain| {
+        prepare_languages_url(domain, display_language)
+    })
+    .await?;
+
+    get_languages(&result_html)
+}
 
-    // send req
-    let url = prepare_url(target_language, source_language, text);
-    let response = client
-        .get(&url)
-        .timeout(std::time::Duration::from_secs(timeout as u64))
-        .send()?;
-    let result_html = response.text()?;
+pub fn send_sync_languages_request(
+    display_language: &str,
+    config: &RequestConfig<'_>,
+) -> Result<Vec<Language>, translator::Error> {
+    let (_, result_html) = get_sync(config, TRANSLATE_DOMAIN, |domain| {
+        prepare_languages_url(domain, display_language)
+    })?;
 
-    get_translated_text(&result_html)
+    get_languages(&result_html)
 }
 
-fn prepare_url(target_language: &str, source_language: &str, text: &str) -> String {
+/// The JSON API on `*.googleapis.com` domains, the mobile page on the other ones.
+fn prepare_url(domain: &str, target_language: &str, source_language: &str, text: &str) -> String {
+    if is_api_domain(domain) {
+        return prepare_api_url(domain, target_language, source_language, text);
+    }
     let escaped_text = encode(text);
     let url = format!(
-        "https://translate.google.com/m?tl={}&sl={}&q={}",
-        target_language, source_language, escaped_text
+        "{}/m?tl={}&sl={}&q={}",
+        base_url(domain),
+        target_language,
+        source_language,
+        escaped_text
     );
 
     url
 }
 
-fn get_translated_text(html: &str) -> Result<String, translator::Error> {
+fn prepare_api_url(
+    domain: &str,
+    target_language: &str,
+    source_language: &str,
+    text: &str,
+) -> String {
+    let source_language = if source_language.is_empty() {
+        "auto"
+    } else {
+        source_language
+    };
+    format!(
+        "{}/translate_a/single?client=gtx&sl={}&tl={}&dt=t&q={}",
+        base_url(domain),
+        source_language,
+        target_language,
+        encode(text)
+    )
+}
+
+/// `https://` is added to the domains without scheme.
+fn base_url(domain: &str) -> String {
+    let domain = domain.trim_end_matches('/');
+    if domain.contains("://") {
+        domain.to_string()
+    } else {
+        format!("https://{domain}")
+    }
+}
+
+fn is_api_domain(domain: &str) -> bool {
+    domain.trim_end_matches('/').ends_with("googleapis.com")
+}
+
+/// Sends the request to the next domain, and to the other ones after a captcha or a 429.
+///
+/// Returns the domain that answered, with the body.
+fn get_sync(
+    config: &RequestConfig<'_>,
+    default_domain: &str,
+    url: impl Fn(&str) -> String,
+) -> Result<(String, String), translator::Error> {
+    let permit = config
+        .adaptive_concurrency
+        .map(AdaptiveConcurrency::acquire_sync);
+    let circuit = config.acquire()?;
+    let result = get_any_domain_sync(config, default_domain, url);
+    config.record(&result, permit.as_ref(), circuit.as_ref());
+    result
+}
+
+#[cfg(feature = "async")]
+async fn get_async(
+    config: &RequestConfig<'_>,
+    default_domain: &str,
+    url: impl Fn(&str) -> String,
+) -> Result<(String, String), translator::Error> {
+    let permit = match config.adaptive_concurrency {
+        Some(concurrency) => Some(concurrency.acquire_async().await),
+        None => None,
+    };
+    let circuit = config.acquire()?;
+    let result = match config.hedging {
+        Some(hedging) => {
+            // the duplicate goes through the proxy of the hedging if set
+            let hedge_config = RequestConfig {
+                proxy_address: hedging.proxy_address().or(config.proxy_address),
+                ..*config
+            };
+            hedging
+                .race(
+                    get_any_domain_async(config, default_domain, &url),
+                    || async {
+                        // the duplicate is one more request in flight, it waits for its own permit
+                        let _permit = match config.adaptive_concurrency {
+                            Some(concurrency) => Some(concurrency.acquire_async().await),
+                            None => None,
+                        };
+                        get_any_domain_async(&hedge_config, default_domain, &url).await
+                    },
+                )
+                .await
+        }
+        None => get_any_domain_async(config, default_domain, url).await,
+    };
+    config.record(&result, permit.as_ref(), circuit.as_ref());
+    result
+}
+
+fn get_any_domain_sync(
+    config: &RequestConfig<'_>,
+    default_domain: &str,
+    url: impl Fn(&str) -> String,
+) -> Result<(String, String), translator::Error> {
+    let mut attempts = config.domain_attempts();
+    loop {
+        let domain = config.domain(default_domain);
+        let request = config.request(&url(&domain));
+        let response = config.transport.send_sync(&request)?;
+        config.store_cookies(&response);
+        match check_response(response) {
+            Ok(body) => return Ok((domain, body)),
+            Err(e) => {
+                config.quarantine(&request, &domain, &e);
+                attempts -= 1;
+                if attempts == 0 || !is_blocked(&e) {
+                    return Err(e);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+async fn get_any_domain_async(
+    config: &RequestConfig<'_>,
+    default_domain: &str,
+    url: impl Fn(&str) -> String,
+) -> Result<(String, String), translator::Error> {
+    let mut attempts = config.domain_attempts();
+    loop {
+        let domain = config.domain(default_domain);
+        let request = config.request(&url(&domain));
+        let response = config.transport.send_async(&request).await?;
+        config.store_cookies(&response);
+        match check_response(response) {
+            Ok(body) => return Ok((domain, body)),
+            Err(e) => {
+                config.quarantine(&request, &domain, &e);
+                attempts -= 1;
+                if attempts == 0 || !is_blocked(&e) {
+                    return Err(e);
+                }
+            }
+        }
+    }
+}
+
+/// Captcha or 429, another domain or address can still work.
+fn is_blocked(error: &translator::Error) -> bool {
+    matches!(
+        error.kind(),
+        ErrorKind::Captcha | ErrorKind::RateLimited { .. }
+    )
+}
+
+/// Google doesn't answer, e.g. the connections of a banned address are dropped.
+fn is_unreachable(error: &translator::Error) -> bool {
+    matches!(error.kind(), ErrorKind::ConnectFailed | ErrorKind::Timeout)
+}
+
+/// Google answers with a captcha page ("/sorry/") when it blocks an ip.
+fn check_response(response: Response) -> Result<String, translator::Error> {
+    if response.path().starts_with("/sorry") || response.body.contains("g-recaptcha") {
+        return Err(
+            translator::Error::new(ErrorKind::Captcha, response.url).with_status(response.status)
+        );
+    }
+    if response.status == 429 {
+        return Err(
+            translator::Error::new(shared::rate_limited(&response), response.url)
+                .with_status(response.status),
+        );
+    }
+    if !(200..300).contains(&response.status) {
+        return Err(
+            translator::Error::new(ErrorKind::Status, response.url).with_status(response.status)
+        );
+    }
+
+    Ok(response.body)
+}
+
+fn get_translated_text(domain: &str, result: &str) -> Result<String, translator::Error> {
+    if is_api_domain(domain) {
+        return get_detection(result).map(|(translated_text, _)| translated_text);
+    }
+    let html = result;
     // extracting translation text
     let pattern = Regex::new(r#"(?s)class="(?:t0|result-container)">(.*?)<"#).unwrap();
     if let Some(captures) = pattern.captures(html) {
         Ok(decode_html_entities(&captures[1]).to_string())
     } else {
-        Err(translator::Error::InvalidRequest("".to_string()))
+        Err(translator::Error::new(
+            ErrorKind::InvalidRequest,
+            "translation not found in the response",
+        ))
     }
 }
+
+/// The API domains have no languages page, the default domain is used instead.
+fn prepare_languages_url(domain: &str, display_language: &str) -> String {
+    let domain = if is_api_domain(domain) {
+        TRANSLATE_DOMAIN
+    } else {
+        domain
+    };
+    format!(
+        "{}/m?mui=tl&hl={}",
+        base_url(domain),
+        encode(display_language)
+    )
+}
+
+fn get_detection(json: &str) -> Result<(String, Detection), translator::Error> {
+    // [[["Bonjour","Hello",...],...],null,"en",null,null,null,0.98,...]
+    let value: Value = serde_json::from_str(json)
+        .map_err(|e| translator::Error::new(ErrorKind::DecodeBody, e.to_string()).with_source(e))?;
+    let translated_text = value
+        .get(0)
+        .and_then(Value::as_array)
+        .map(|sentences| {
+            sentences
+                .iter()
+                .filter_map(|sentence| sentence.get(0).and_then(Value::as_str))
+                .collect::<String>()
+        })
+        .ok_or_else(|| translator::Error::new(ErrorKind::DecodeBody, json))?;
+    let language = value
+        .get(2)
+        .and_then(Value::as_str)
+        .ok_or_else(|| translator::Error::new(ErrorKind::LanguageDetection, json))?;
+    let confidence = value.get(6).and_then(Value::as_f64).unwrap_or(1.0);
+
+    Ok((
+        translated_text,
+        Detection {
+            language: language.to_string(),
+            confidence,
+        },
+    ))
+}
@@ -0,0 +1,170 @@
+use crate::translators::runtime;
+
+use futures::future::{self, Either};
+use std::collections::VecDeque;
+use std::fmt;
+use std::future::Future;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+/// Number of latencies kept for the percentile.
+const WINDOW: usize = 100;
+/// Latencies needed before the percentile is used instead of the fixed delay.
+const MIN_SAMPLES: usize = 20;
+
+/// Sends a duplicate of a slow async request and keeps the first successful answer.
+///
+/// The duplicate is sent after a fixed `delay`, or after a percentile of the recent
+/// latencies, optionally through another proxy. The budget limits the share of the
+/// requests that are duplicated, so a slow service doesn't get twice the load.
+/// Clones share the latencies and the budget.
+///
+/// ```ignore
+/// // duplicate the requests slower than 95% of the recent ones, at least 500ms,
+/// // at most 5% of the requests
+/// let hedging = Hedging::new(Duration::from_millis(500))
+///     .with_percentile(0.95)
+///     .with_budget(0.05)
+///     .with_proxy_address("socks5://127.0.0.1:9050");
+/// ```
+#[derive(Clone)]
+pub struct Hedging {
+    delay: Duration,
+    percentile: Option<f64>,
+    budget: f64,
+    proxy_address: Option<String>,
+    state: Arc<Mutex<State>>,
+}
+
+#[derive(Default)]
+struct State {
+    /// Latencies of the last successful requests.
+    latencies: VecDeque<Duration>,
+    requests: u64,
+    hedged: u64,
+}
+
+impl Hedging {
+    /// Duplicates the requests without answer after `delay`, up to 10% of the requests.
+    pub fn new(delay: Duration) -> Self {
+        Hedging {
+            delay,
+            percentile: None,
+            budget: 0.1,
+            proxy_address: None,
+            state: Arc::new(Mutex::new(State::default())),
+        }
+    }
+
+    /// Waits for this percentile of the recent latencies (between 0 and 1) instead,
+    /// never less than the delay.
+    ///
+    /// The fixed delay is used until 20 requests succeeded.
+    pub fn with_percentile(mut self, percentile: f64) -> Self {
+        self.percentile = Some(percentile.clamp(0.0, 1.0));
+        self
+    }
+
+    /// Share of the requests that can be duplicated, between 0 and 1.
+    pub fn with_budget(mut self, budget: f64) -> Self {
+        self.budget = budget.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Sends the duplicates through this proxy, see `GoogleTranslator::proxy_address`.
+    pub fn with_proxy_address(mut self, proxy_address: impl Into<String>) -> Self {
+        self.proxy_address = Some(proxy_address.into());
+        self
+    }
+
+    pub fn proxy_address(&self) -> Option<&str> {
+        self.proxy_address.as_deref()
+    }
+
+    /// How long a request can take before its duplicate is sent.
+    pub fn delay(&self) -> Duration {
+        let state = self.lock();
+        let Some(percentile) = self.percentile else {
+            return self.delay;
+        };
+        if state.latencies.len() < MIN_SAMPLES {
+            return self.delay;
+        }
+        let mut latencies: Vec<_> = state.latencies.iter().copied().collect();
+        latencies.sort_unstable();
+        let index = ((latencies.len() - 1) as f64 * percentile).round() as usize;
+        latencies[index].max(self.delay)
+    }
+
+    /// Number of requests duplicated so far.
+    pub fn hedged(&self) -> u64 {
+        self.lock().hedged
+    }
+
+    /// Runs `request`, and `hedge` too if the request is still running after the delay
+    /// and the budget allows it.
+    ///
+    /// Returns the first success, or the error of the last one to fail.
+    pub async fn race<T, E, F, H, HF>(&self, request: F, hedge: H) -> Result<T, E>
+    where
+        F: Future<Output = Result<T, E>>,
+        H: FnOnce() -> HF,
+        HF: Future<Output = Result<T, E>>,
+    {
+        let start = Instant::now();
+        self.lock().requests += 1;
+        let mut request = std::pin::pin!(request);
+        let result = match runtime::timeout(self.delay(), request.as_mut()).await {
+            Some(result) => result,
+            None if self.spend() => {
+                let hedge = std::pin::pin!(hedge());
+                match future::select(request, hedge).await {
+                    Either::Left((Ok(answer), _)) | Either::Right((Ok(answer), _)) => Ok(answer),
+                    Either::Left((Err(_), other)) => other.await,
+                    Either::Right((Err(_), other)) => other.await,
+                }
+            }
+            None => request.await,
+        };
+        if result.is_ok() {
+            self.record_latency(start.elapsed());
+        }
+
+        result
+    }
+
+    /// Takes a duplicate from the budget, `false` if it is used up.
+    fn spend(&self) -> bool {
+        let mut state = self.lock();
+        let allowed = (state.requests as f64 * self.budget).ceil() as u64;
+        if state.hedged >= allowed {
+            return false;
+        }
+        state.hedged += 1;
+        true
+    }
+
+    fn record_latency(&self, latency: Duration) {
+        let mut state = self.lock();
+        if state.latencies.len() == WINDOW {
+            state.latencies.pop_front();
+        }
+        state.latencies.push_back(latency);
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap()
+    }
+}
+
+impl fmt::Debug for Hedging {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Hedging")
+            .field("delay", &self.delay())
+            .field("percentile", &self.percentile)
+            .field("budget", &self.budget)
+            .field("proxy_address", &self.proxy_address)
+            .field("hedged", &self.hedged())
+            .finish()
+    }
+}
@@ -0,0 +1,193 @@
+use crate::translators::detector::Detection;
+use crate::translators::translator::shared::{self, Connection};
+use crate::translators::translator::{self, ErrorKind, Language};
+use crate::translators::transport::{Request, Response};
+use serde_json::{json, Value};
+
+/// Settings of a single request.
+#[derive(Debug, Clone, Copy)]
+pub struct RequestConfig<'a> {
+    pub connection: Connection<'a>,
+    /// Url of the LibreTranslate server, without the endpoint.
+    pub url: &'a str,
+    pub api_key: Option<&'a str>,
+}
+
+impl RequestConfig<'_> {
+    fn request(&self, request: Request) -> Request {
+        self.connection.request(request)
+    }
+
+    /// `POST` of a JSON body, with the api key if set.
+    fn post(&self, endpoint: &str, mut body: Value) -> Request {
+        if let Some(api_key) = self.api_key {
+            body["api_key"] = Value::from(api_key);
+        }
+        self.request(Request::post(self.endpoint_url(endpoint), body.to_string()))
+            .header("Content-Type", "application/json")
+            .header("Accept", "application/json")
+    }
+
+    fn get(&self, endpoint: &str) -> Request {
+        self.request(Request::get(self.endpoint_url(endpoint)))
+            .header("Accept", "application/json")
+    }
+
+    fn endpoint_url(&self, endpoint: &str) -> String {
+        format!("{}/{}", self.url.trim_end_matches('/'), endpoint)
+    }
+}
+
+#[cfg(feature = "async")]
+pub async fn send_async_request(
+    target_language: &str,
+    source_language: &str,
+    text: &str,
+    config: &RequestConfig<'_>,
+) -> Result<String, translator::Error> {
+    let request = config.post(
+        "translate",
+        translate_body(target_language, source_language, text),
+    );
+    let response = config.connection.transport.send_async(&request).await?;
+
+    get_translated_text(&check_response(response)?)
+}
+
+pub fn send_sync_request(
+    target_language: &str,
+    source_language: &str,
+    text: &str,
+    config: &RequestConfig<'_>,
+) -> Result<String, translator::Error> {
+    let request = config.post(
+        "translate",
+        translate_body(target_language, source_language, text),
+    );
+    let response = config.connection.transport.send_sync(&request)?;
+
+    get_translated_text(&check_response(response)?)
+}
+
+#[cfg(feature = "async")]
+pub async fn send_async_detect_request(
+    text: &str,
+    config: &RequestConfig<'_>,
+) -> Result<Detection, translator::Error> {
+    let request = config.post("detect", json!({ "q": text }));
+    let response = config.connection.transport.send_async(&request).await?;
+
+    get_detection(&check_response(response)?)
+}
+
+pub fn send_sync_detect_request(
+    text: &str,
+    config: &RequestConfig<'_>,
+) -> Result<Detection, translator::Error> {
+    let request = config.post("detect", json!({ "q": text }));
+    let response = config.connection.transport.send_sync(&request)?;
+
+    get_detection(&check_response(response)?)
+}
+
+#[cfg(feature = "async")]
+pub async fn send_async_languages_request(
+    config: &RequestConfig<'_>,
+) -> Result<Vec<Language>, translator::Error> {
+    let request = config.get("languages");
+    let response = config.connection.transport.send_async(&request).await?;
+
+    get_languages(&check_response(response)?)
+}
+
+pub fn send_sync_languages_request(
+    config: &RequestConfig<'_>,
+) -> Result<Vec<Language>, translator::Error> {
+    let request = config.get("languages");
+    let response = config.connection.transport.send_sync(&request)?;
+
+    get_languages(&check_response(response)?)
+}
+
+/// LibreTranslate uses `"auto"` for the detection of the source language.
+fn translate_body(target_language: &str, source_language: &str, text: &str) -> Value {
+    let source_language = if source_language.is_empty() {
+        "auto"
+    } else {
+        source_language
+    };
+    json!({
+        "q": text,
+        "source": source_language,
+        "target": target_language,
+        "format": "text",
+    })
+}
+
+/// LibreTranslate answers the errors with `{"error": "..."}`.
+fn check_response(response: Response) -> Result<Value, translator::Error> {
+    let value: Option<Value> = serde_json::from_str(&response.body).ok();
+    if (200..300).contains(&response.status) {
+        return value.ok_or_else(|| translator::Error::new(ErrorKind::DecodeBody, response.body));
+    }
+
+    let message = value
+        .as_ref()
+        .and_then(|value| value.get("error"))
+        .and_then(Value::as_str)
+        .map_or_else(|| response.url.clone(), str::to_string);
+    let kind = match response.status {
+        400 => ErrorKind::InvalidRequest,
+        429 => shared::rate_limited(&response),
+        _ => ErrorKind::Status,
+    };
+    Err(translator::Error::new(kind, message).with_status(response.status))
+}
+
+fn get_translated_text(value: &Value) -> Result<String, translator::Error> {
+    // {"translatedText": "Bonjour", "detectedLanguage": {...}}
+    value
+        .get("translatedText")
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .ok_or_else(|| translator::Error::new(ErrorKind::DecodeBody, value.to_string()))
+}
+
+fn get_detection(value: &Value) -> Result<Detection, translator::Error> {
+    // [{"confidence": 90.0, "language": "fr"}, ...], best first
+    let best = value
+        .as_array()
+        .and_then(|detections| detections.first())
+        .ok_or_else(|| translator::Error::new(ErrorKind::LanguageDetection, value.to_string()))?;
+    let language = best
+        .get("language")
+        .and_then(Value::as_str)
+        .ok_or_else(|| translator::Error::new(ErrorKind::DecodeBody, value.to_string()))?;
+    // percentage
+    let confidence = best
+        .get("confidence")
+        .and_then(Value::as_f64)
+        .map_or(1.0, |confidence| confidence / 100.0);
+
+    Ok(Detection {
+        language: language.to_string(),
+        confidence,
+    })
+}
+
+fn get_languages(value: &Value) -> Result<Vec<Language>, translator::Error> {
+    // [{"code": "en", "name": "English", "targets": [...]}, ...]
+    let languages = value
+        .as_array()
+        .ok_or_else(|| translator::Error::new(ErrorKind::DecodeBody, value.to_string()))?;
+
+    Ok(languages
+        .iter()
+        .filter_map(|language| {
+            Some(Language {
+                code: language.get("code")?.as_str()?.to_string(),
+                name: language.get("name")?.as_str()?.to_string(),
+            })
+        })
+        .collect())
+}
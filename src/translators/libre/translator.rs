@@ -0,0 +1,215 @@
+use crate::translators::chunks::{detection_sample, Chunking};
+use crate::translators::detector::{Detection, LanguageDetector};
+use crate::translators::libre::languages;
+#[cfg(feature = "async")]
+use crate::translators::libre::requests::{
+    send_async_detect_request, send_async_languages_request, send_async_request,
+};
+use crate::translators::libre::requests::{
+    send_sync_detect_request, send_sync_languages_request, send_sync_request, RequestConfig,
+};
+use crate::translators::registry::BackendConfig;
+use crate::translators::translator::shared::{
+    self, chunking, connection, read_shared_config, validate_target,
+};
+use crate::translators::translator::{self, Language};
+use crate::translators::transport::{self, TlsConfig, Transport};
+
+use macon::Builder;
+#[cfg(feature = "async")]
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// [LibreTranslate](https://libretranslate.com), self-hosted or a public instance.
+///
+/// # Dependencies:
+/// Add to your dependency:
+/// ```no_run ignore
+/// [dependencies]
+/// translators = { version = "0.1.5", features = ["libre", "tokio-async"] }
+/// ```
+/// # Examples
+///
+/// ```no_run ignore
+/// use translators::{LibreTranslator, Translator};
+///
+/// fn main() {
+///     let libre_trans = LibreTranslator::builder()
+///         .url("http://localhost:5000")
+///         // only for the servers that need one
+///         .api_key("xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx")
+///         .build();
+///     let translated_text = libre_trans
+///         .translate_sync("Hello, world!", "", "es")
+///         .unwrap();
+///     println!("{}", translated_text);
+/// }
+/// ```
+///
+/// Long texts are split in chunks of `text_limit` chars, like with
+/// [`crate::GoogleTranslator`]. The other settings are the same too.
+#[derive(Builder, Clone, Debug)]
+#[builder(mode=Panic, Default)]
+pub struct LibreTranslator {
+    /// Url of the server, `http://localhost:5000` by default.
+    pub url: String,
+    /// Key of the servers started with `--api-keys`.
+    pub api_key: Option<String>,
+    /// How long to wait for each request, from connect to the end of the body.
+    pub request_timeout: Duration,
+    /// How long to wait for the connection to the server.
+    pub connect_timeout: Option<Duration>,
+    /// How long a whole `translate_*` call can take, all chunks included.
+    pub total_timeout: Option<Duration>,
+    /// Delay between requests.
+    pub request_delay: Duration,
+    /// Proxy address, see [`crate::GoogleTranslator`] for the format.
+    pub proxy_address: Option<String>,
+    /// How many requests can be handled concurrently.
+    ///
+    /// Unlimited in async by default. The sync API translates the chunks on up to
+    /// `max_concurrency` threads, one at a time if unset.
+    pub max_concurrency: Option<usize>,
+    /// Maximum number of chars of a request, the `--char-limit` of the server.
+    pub text_limit: usize,
+    /// Headers added to every request.
+    pub headers: Vec<(String, String)>,
+    /// Root certificates and client identity, e.g. for a server with a private CA.
+    pub tls: Option<Arc<TlsConfig>>,
+    /// Sends the requests, reqwest by default (ureq without the `reqwest` feature).
+    #[builder(Into=!)]
+    pub transport: Arc<dyn Transport>,
+}
+
+impl translator::Translator for LibreTranslator {
+    #[cfg(feature = "async")]
+    async fn translate_async(
+        &self,
+        text: &str,
+        source_language: &str,
+        target_language: &str,
+    ) -> Result<String, translator::Error> {
+        validate_target(target_language, BACKEND)?;
+        let config = self.request_config();
+        self.chunking()
+            .translate_async(text, |chunk| {
+                send_async_request(target_language, source_language, chunk, &config)
+            })
+            .await
+    }
+
+    fn translate_sync(
+        &self,
+        text: &str,
+        source_language: &str,
+        target_language: &str,
+    ) -> Result<String, translator::Error> {
+        validate_target(target_language, BACKEND)?;
+        self.chunking().translate_sync(text, |chunk, time_left| {
+            let mut config = self.request_config();
+            if let Some(time_left) = time_left {
+                config.connection.timeout = config.connection.timeout.min(time_left);
+            }
+            send_sync_request(target_language, source_language, chunk, &config)
+        })
+    }
+
+    /// Languages of the libretranslate.com models.
+    ///
+    /// Use [`LibreTranslator::fetch_languages_sync`] to get the ones of the server.
+    fn supported_languages(&self) -> Vec<Language> {
+        shared::languages(languages::LANGUAGES)
+    }
+}
+
+impl LibreTranslator {
+    /// Builds a translator from string settings, missing keys keep the default values.
+    ///
+    /// Keys are the field names: `url`, `api_key`, `request_timeout`, `connect_timeout`,
+    /// `total_timeout`, `request_delay` (in seconds), `proxy_address`, `max_concurrency`
+    /// and `text_limit`.
+    pub fn from_config(config: &BackendConfig) -> Result<Self, translator::Error> {
+        let mut translator = LibreTranslator::default();
+        if let Some(url) = config.get("url") {
+            translator.url = url.to_string();
+        }
+        if let Some(api_key) = config.get("api_key") {
+            translator.api_key = Some(api_key.to_string());
+        }
+        read_shared_config!(translator, config);
+
+        Ok(translator)
+    }
+
+    fn chunking(&self) -> Chunking {
+        chunking!(self, BACKEND)
+    }
+
+    fn request_config(&self) -> RequestConfig<'_> {
+        RequestConfig {
+            connection: connection!(self),
+            url: &self.url,
+            api_key: self.api_key.as_deref(),
+        }
+    }
+
+    /// Fetches the languages of the server.
+    #[cfg(feature = "async")]
+    pub async fn fetch_languages_async(&self) -> Result<Vec<Language>, translator::Error> {
+        send_async_languages_request(&self.request_config())
+            .await
+            .map_err(|e| e.with_backend(BACKEND))
+    }
+
+    /// Fetches the languages of the server.
+    pub fn fetch_languages_sync(&self) -> Result<Vec<Language>, translator::Error> {
+        send_sync_languages_request(&self.request_config()).map_err(|e| e.with_backend(BACKEND))
+    }
+}
+
+/// Detects the language with the `/detect` endpoint, the confidence is in `0.0..=1.0`.
+///
+/// Only the first `text_limit` chars are sent, this is enough to detect the language.
+impl LanguageDetector for LibreTranslator {
+    #[cfg(feature = "async")]
+    fn detect_async(
+        &self,
+        text: &str,
+    ) -> impl Future<Output = Result<Detection, translator::Error>> + Send {
+        let sample = detection_sample(text, self.text_limit);
+        async move {
+            send_async_detect_request(sample, &self.request_config())
+                .await
+                .map_err(|e| e.with_backend(BACKEND))
+        }
+    }
+
+    fn detect_sync(&self, text: &str) -> Result<Detection, translator::Error> {
+        let sample = detection_sample(text, self.text_limit);
+        send_sync_detect_request(sample, &self.request_config())
+            .map_err(|e| e.with_backend(BACKEND))
+    }
+}
+
+impl Default for LibreTranslator {
+    fn default() -> Self {
+        LibreTranslator {
+            url: "http://localhost:5000".to_string(),
+            api_key: None,
+            request_timeout: Duration::from_secs(35),
+            connect_timeout: None,
+            total_timeout: None,
+            request_delay: Duration::ZERO,
+            proxy_address: None,
+            max_concurrency: None,
+            text_limit: 5000,
+            headers: Vec::new(),
+            tls: None,
+            transport: transport::default_transport(),
+        }
+    }
+}
+
+/// Name of the translator in the errors.
+const BACKEND: &str = "libre";
@@ -0,0 +1,130 @@
+use crate::translators::translator::shared::{self, Connection};
+use crate::translators::translator::{self, ErrorKind, Language};
+use crate::translators::transport::{Request, Response};
+use serde_json::Value;
+use urlencoding::encode;
+
+/// Settings of a single request.
+#[derive(Debug, Clone, Copy)]
+pub struct RequestConfig<'a> {
+    pub connection: Connection<'a>,
+    /// Url of the Lingva instance, without the endpoint.
+    pub url: &'a str,
+}
+
+impl RequestConfig<'_> {
+    fn get(&self, endpoint: &str) -> Request {
+        let url = format!("{}/api/v1/{}", self.url.trim_end_matches('/'), endpoint);
+        self.connection
+            .request(Request::get(url))
+            .header("Accept", "application/json")
+    }
+}
+
+#[cfg(feature = "async")]
+pub async fn send_async_request(
+    target_language: &str,
+    source_language: &str,
+    text: &str,
+    config: &RequestConfig<'_>,
+) -> Result<String, translator::Error> {
+    let request = config.get(&translate_endpoint(target_language, source_language, text));
+    let response = config.connection.transport.send_async(&request).await?;
+
+    get_translated_text(&check_response(response)?)
+}
+
+pub fn send_sync_request(
+    target_language: &str,
+    source_language: &str,
+    text: &str,
+    config: &RequestConfig<'_>,
+) -> Result<String, translator::Error> {
+    let request = config.get(&translate_endpoint(target_language, source_language, text));
+    let response = config.connection.transport.send_sync(&request)?;
+
+    get_translated_text(&check_response(response)?)
+}
+
+#[cfg(feature = "async")]
+pub async fn send_async_languages_request(
+    config: &RequestConfig<'_>,
+) -> Result<Vec<Language>, translator::Error> {
+    let request = config.get("languages/target");
+    let response = config.connection.transport.send_async(&request).await?;
+
+    get_languages(&check_response(response)?)
+}
+
+pub fn send_sync_languages_request(
+    config: &RequestConfig<'_>,
+) -> Result<Vec<Language>, translator::Error> {
+    let request = config.get("languages/target");
+    let response = config.connection.transport.send_sync(&request)?;
+
+    get_languages(&check_response(response)?)
+}
+
+/// `{source}/{target}/{text}`, Lingva uses `"auto"` for the detection of the source language.
+fn translate_endpoint(target_language: &str, source_language: &str, text: &str) -> String {
+    let source_language = if source_language.is_empty() {
+        "auto"
+    } else {
+        source_language
+    };
+    format!(
+        "{}/{}/{}",
+        encode(source_language),
+        encode(target_language),
+        encode(text)
+    )
+}
+
+/// Lingva answers the errors with `{"error": "..."}`.
+fn check_response(response: Response) -> Result<Value, translator::Error> {
+    let value: Option<Value> = serde_json::from_str(&response.body).ok();
+    if (200..300).contains(&response.status) {
+        return value.ok_or_else(|| translator::Error::new(ErrorKind::DecodeBody, response.body));
+    }
+
+    let message = value
+        .as_ref()
+        .and_then(|value| value.get("error"))
+        .and_then(Value::as_str)
+        .map_or_else(|| response.url.clone(), str::to_string);
+    let kind = match response.status {
+        // "Invalid target language", "Invalid source language"
+        400 => ErrorKind::InvalidRequest,
+        // the instance or Google limits the requests
+        429 => shared::rate_limited(&response),
+        _ => ErrorKind::Status,
+    };
+    Err(translator::Error::new(kind, message).with_status(response.status))
+}
+
+fn get_translated_text(value: &Value) -> Result<String, translator::Error> {
+    // {"translation": "Bonjour", "info": {...}}
+    value
+        .get("translation")
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .ok_or_else(|| translator::Error::new(ErrorKind::DecodeBody, value.to_string()))
+}
+
+fn get_languages(value: &Value) -> Result<Vec<Language>, translator::Error> {
+    // {"languages": [{"code": "en", "name": "English"}, ...]}
+    let languages = value
+        .get("languages")
+        .and_then(Value::as_array)
+        .ok_or_else(|| translator::Error::new(ErrorKind::DecodeBody, value.to_string()))?;
+
+    Ok(languages
+        .iter()
+        .filter_map(|language| {
+            Some(Language {
+                code: language.get("code")?.as_str()?.to_string(),
+                name: language.get("name")?.as_str()?.to_string(),
+            })
+        })
+        .collect())
+}
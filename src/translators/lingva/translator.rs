@@ -0,0 +1,178 @@
+use crate::translators::chunks::Chunking;
+use crate::translators::lingva::languages;
+#[cfg(feature = "async")]
+use crate::translators::lingva::requests::{send_async_languages_request, send_async_request};
+use crate::translators::lingva::requests::{
+    send_sync_languages_request, send_sync_request, RequestConfig,
+};
+use crate::translators::registry::BackendConfig;
+use crate::translators::translator::shared::{
+    self, chunking, connection, read_shared_config, validate_target,
+};
+use crate::translators::translator::{self, Language};
+use crate::translators::transport::{self, TlsConfig, Transport};
+
+use macon::Builder;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// [Lingva](https://github.com/thedaviddelta/lingva-translate), an alternative front-end
+/// for Google Translate, self-hosted or a public instance.
+///
+/// # Dependencies:
+/// Add to your dependency:
+/// ```no_run ignore
+/// [dependencies]
+/// translators = { version = "0.1.5", features = ["lingva", "tokio-async"] }
+/// ```
+/// # Examples
+///
+/// ```no_run ignore
+/// use translators::{Translator, LingvaTranslator};
+///
+/// fn main() {
+///     let lingva_trans = LingvaTranslator::builder()
+///         .url("https://lingva.example.org")
+///         .build();
+///     let translated_text = lingva_trans
+///         .translate_sync("Hello, world!", "", "es")
+///         .unwrap();
+///     println!("{}", translated_text);
+/// }
+/// ```
+///
+/// Long texts are split in chunks of `text_limit` chars, like with
+/// [`crate::GoogleTranslator`]. The other settings are the same too.
+#[derive(Builder, Clone, Debug)]
+#[builder(mode=Panic, Default)]
+pub struct LingvaTranslator {
+    /// Url of the instance, `https://lingva.ml` by default.
+    pub url: String,
+    /// How long to wait for each request, from connect to the end of the body.
+    pub request_timeout: Duration,
+    /// How long to wait for the connection to the server.
+    pub connect_timeout: Option<Duration>,
+    /// How long a whole `translate_*` call can take, all chunks included.
+    pub total_timeout: Option<Duration>,
+    /// Delay between requests.
+    pub request_delay: Duration,
+    /// Proxy address, see [`crate::GoogleTranslator`] for the format.
+    pub proxy_address: Option<String>,
+    /// How many requests can be handled concurrently.
+    ///
+    /// Unlimited in async by default. The sync API translates the chunks on up to
+    /// `max_concurrency` threads, one at a time if unset.
+    pub max_concurrency: Option<usize>,
+    /// Maximum number of chars of a request, the text is a part of the url.
+    pub text_limit: usize,
+    /// Headers added to every request.
+    pub headers: Vec<(String, String)>,
+    /// Root certificates and client identity, e.g. for a TLS-inspecting proxy.
+    pub tls: Option<Arc<TlsConfig>>,
+    /// Sends the requests, reqwest by default (ureq without the `reqwest` feature).
+    #[builder(Into=!)]
+    pub transport: Arc<dyn Transport>,
+}
+
+impl translator::Translator for LingvaTranslator {
+    #[cfg(feature = "async")]
+    async fn translate_async(
+        &self,
+        text: &str,
+        source_language: &str,
+        target_language: &str,
+    ) -> Result<String, translator::Error> {
+        validate_target(target_language, BACKEND)?;
+        let config = self.request_config();
+        self.chunking()
+            .translate_async(text, |chunk| {
+                send_async_request(target_language, source_language, chunk, &config)
+            })
+            .await
+    }
+
+    fn translate_sync(
+        &self,
+        text: &str,
+        source_language: &str,
+        target_language: &str,
+    ) -> Result<String, translator::Error> {
+        validate_target(target_language, BACKEND)?;
+        self.chunking().translate_sync(text, |chunk, time_left| {
+            let mut config = self.request_config();
+            if let Some(time_left) = time_left {
+                config.connection.timeout = config.connection.timeout.min(time_left);
+            }
+            send_sync_request(target_language, source_language, chunk, &config)
+        })
+    }
+
+    /// A part of the Google languages.
+    ///
+    /// Use [`LingvaTranslator::fetch_languages_sync`] to get the ones of the instance.
+    fn supported_languages(&self) -> Vec<Language> {
+        shared::languages(languages::LANGUAGES)
+    }
+}
+
+impl LingvaTranslator {
+    /// Builds a translator from string settings, missing keys keep the default values.
+    ///
+    /// Keys are the field names: `url`, `request_timeout`, `connect_timeout`,
+    /// `total_timeout`, `request_delay` (in seconds), `proxy_address`, `max_concurrency`
+    /// and `text_limit`.
+    pub fn from_config(config: &BackendConfig) -> Result<Self, translator::Error> {
+        let mut translator = LingvaTranslator::default();
+        if let Some(url) = config.get("url") {
+            translator.url = url.to_string();
+        }
+        read_shared_config!(translator, config);
+
+        Ok(translator)
+    }
+
+    fn chunking(&self) -> Chunking {
+        chunking!(self, BACKEND)
+    }
+
+    fn request_config(&self) -> RequestConfig<'_> {
+        RequestConfig {
+            connection: connection!(self),
+            url: &self.url,
+        }
+    }
+
+    /// Fetches the target languages of the instance.
+    #[cfg(feature = "async")]
+    pub async fn fetch_languages_async(&self) -> Result<Vec<Language>, translator::Error> {
+        send_async_languages_request(&self.request_config())
+            .await
+            .map_err(|e| e.with_backend(BACKEND))
+    }
+
+    /// Fetches the target languages of the instance.
+    pub fn fetch_languages_sync(&self) -> Result<Vec<Language>, translator::Error> {
+        send_sync_languages_request(&self.request_config()).map_err(|e| e.with_backend(BACKEND))
+    }
+}
+
+impl Default for LingvaTranslator {
+    fn default() -> Self {
+        LingvaTranslator {
+            url: "https://lingva.ml".to_string(),
+            request_timeout: Duration::from_secs(35),
+            connect_timeout: None,
+            total_timeout: None,
+            request_delay: Duration::ZERO,
+            proxy_address: None,
+            max_concurrency: None,
+            text_limit: 1000,
+            headers: Vec::new(),
+            tls: None,
+            transport: transport::default_transport(),
+        }
+    }
+}
+
+/// Name of the translator in the errors.
+const BACKEND: &str = "lingva";
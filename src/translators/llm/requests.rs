@@ -0,0 +1,134 @@
+use crate::translators::translator::shared::{self, Connection};
+use crate::translators::translator::{self, ErrorKind};
+use crate::translators::transport::{Request, Response};
+use serde_json::{json, Value};
+
+/// Settings of a single request.
+#[derive(Debug, Clone, Copy)]
+pub struct RequestConfig<'a> {
+    pub connection: Connection<'a>,
+    /// Base url of the API, like `http://localhost:8080/v1`.
+    pub url: &'a str,
+    pub api_key: Option<&'a str>,
+    /// Not sent if empty.
+    pub model: &'a str,
+    pub temperature: Option<f32>,
+}
+
+impl RequestConfig<'_> {
+    /// `POST` of the conversation to `/chat/completions`.
+    fn post(&self, messages: Vec<Value>) -> Request {
+        let mut body = json!({ "messages": messages, "stream": false });
+        if !self.model.is_empty() {
+            body["model"] = Value::from(self.model);
+        }
+        if let Some(temperature) = self.temperature {
+            body["temperature"] = Value::from(temperature);
+        }
+        let url = format!("{}/chat/completions", self.url.trim_end_matches('/'));
+        let mut request = self
+            .connection
+            .request(Request::post(url, body.to_string()))
+            .header("Content-Type", "application/json")
+            .header("Accept", "application/json");
+        if let Some(api_key) = self.api_key {
+            request = request.header("Authorization", format!("Bearer {}", api_key));
+        }
+
+        request
+    }
+}
+
+#[cfg(feature = "async")]
+pub async fn send_async_request(
+    system_prompt: &str,
+    previous: &[(&str, String)],
+    text: &str,
+    config: &RequestConfig<'_>,
+) -> Result<String, translator::Error> {
+    let request = config.post(messages(system_prompt, previous, text));
+    let response = config.connection.transport.send_async(&request).await?;
+
+    get_translated_text(&check_response(response)?, text)
+}
+
+pub fn send_sync_request(
+    system_prompt: &str,
+    previous: &[(&str, String)],
+    text: &str,
+    config: &RequestConfig<'_>,
+) -> Result<String, translator::Error> {
+    let request = config.post(messages(system_prompt, previous, text));
+    let response = config.connection.transport.send_sync(&request)?;
+
+    get_translated_text(&check_response(response)?, text)
+}
+
+/// The previous chunks are earlier turns of the conversation, the model keeps the same
+/// terms and style for the text.
+fn messages(system_prompt: &str, previous: &[(&str, String)], text: &str) -> Vec<Value> {
+    let mut messages = vec![json!({"role": "system", "content": system_prompt})];
+    for (chunk, translated_chunk) in previous {
+        messages.push(json!({"role": "user", "content": chunk.trim()}));
+        messages.push(json!({"role": "assistant", "content": translated_chunk.trim()}));
+    }
+    messages.push(json!({"role": "user", "content": text.trim()}));
+
+    messages
+}
+
+/// OpenAI-compatible servers answer the errors with `{"error": {"message": "...", ...}}`.
+fn check_response(response: Response) -> Result<Value, translator::Error> {
+    let value: Option<Value> = serde_json::from_str(&response.body).ok();
+    if (200..300).contains(&response.status) {
+        return value.ok_or_else(|| translator::Error::new(ErrorKind::DecodeBody, response.body));
+    }
+
+    let error = value.as_ref().and_then(|value| value.get("error"));
+    // some servers answer `{"error": "..."}`
+    let message = error
+        .and_then(|error| error.get("message").or(Some(error)))
+        .and_then(Value::as_str)
+        .map_or_else(|| response.url.clone(), str::to_string);
+    let code = error
+        .and_then(|error| error.get("code").or_else(|| error.get("type")))
+        .and_then(Value::as_str);
+    let kind = match (response.status, code) {
+        // the credits of the account are used up
+        (429, Some("insufficient_quota")) => ErrorKind::QuotaExceeded,
+        // also a text longer than the context of the model
+        (400, _) => ErrorKind::InvalidRequest,
+        (429, _) => shared::rate_limited(&response),
+        _ => ErrorKind::Status,
+    };
+    Err(translator::Error::new(kind, message).with_status(response.status))
+}
+
+/// The answer with the whitespace around `text`, the chunks are joined as they are.
+fn get_translated_text(value: &Value, text: &str) -> Result<String, translator::Error> {
+    // {"choices": [{"message": {"role": "assistant", "content": "Bonjour"}, "finish_reason": "stop"}]}
+    let choice = value
+        .get("choices")
+        .and_then(|choices| choices.get(0))
+        .ok_or_else(|| translator::Error::new(ErrorKind::DecodeBody, value.to_string()))?;
+    if choice.get("finish_reason").and_then(Value::as_str) == Some("length") {
+        return Err(translator::Error::new(
+            ErrorKind::InvalidRequest,
+            "the answer was cut at the token limit of the model",
+        ));
+    }
+    let content = choice
+        .get("message")
+        .and_then(|message| message.get("content"))
+        .and_then(Value::as_str)
+        .ok_or_else(|| translator::Error::new(ErrorKind::DecodeBody, value.to_string()))?;
+
+    let start = text.len() - text.trim_start().len();
+    let end = text.trim_end().len().max(start);
+    Ok(format!(
+        "{}{}{}",
+        &text[..start],
+        content.trim(),
+        &text[end..]
+    ))
+}
@@ -0,0 +1,229 @@
+use crate::translators::chunks::Chunking;
+use crate::translators::llm::languages::{self, language_name};
+#[cfg(feature = "async")]
+use crate::translators::llm::requests::send_async_request;
+use crate::translators::llm::requests::{send_sync_request, RequestConfig};
+use crate::translators::registry::BackendConfig;
+use crate::translators::translator::shared::{
+    self, chunking, connection, read_shared_config, validate_target,
+};
+use crate::translators::translator::{self, Language};
+use crate::translators::transport::{self, TlsConfig, Transport};
+
+use macon::Builder;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Translation by a language model behind an OpenAI-compatible `/v1/chat/completions`
+/// API, like the servers of llama.cpp, vLLM or Ollama.
+///
+/// # Dependencies:
+/// Add to your dependency:
+/// ```no_run ignore
+/// [dependencies]
+/// translators = { version = "0.1.5", features = ["llm", "tokio-async"] }
+/// ```
+/// # Examples
+///
+/// ```no_run ignore
+/// use translators::{LlmTranslator, Translator};
+///
+/// fn main() {
+///     let llm_trans = LlmTranslator::builder()
+///         .url("http://localhost:11434/v1")
+///         .model("qwen2.5:7b")
+///         .build();
+///     let translated_text = llm_trans
+///         .translate_sync("Hello, world!", "", "es")
+///         .unwrap();
+///     println!("{}", translated_text);
+/// }
+/// ```
+///
+/// Long texts are split in chunks of `text_limit` chars, each request also has the
+/// `context_chunks` previous chunks with their translations so the terms and the style
+/// stay the same across chunks.
+#[derive(Builder, Clone, Debug)]
+#[builder(mode=Panic, Default)]
+pub struct LlmTranslator {
+    /// Base url of the API, `http://localhost:8080/v1` by default.
+    pub url: String,
+    /// Sent as a bearer token, for the servers that need one.
+    pub api_key: Option<String>,
+    /// Name of the model, not sent if empty for the servers with a single model.
+    pub model: String,
+    /// Instructions of the model, `{source}` and `{target}` are replaced by the names
+    /// of the languages.
+    pub system_prompt: String,
+    /// Sampling temperature, `0.0` by default for stable translations.
+    pub temperature: Option<f32>,
+    /// How many previous chunks are sent with each chunk, `0` translates the chunks
+    /// independently and concurrently.
+    pub context_chunks: usize,
+    /// How long to wait for each request, from connect to the end of the body.
+    pub request_timeout: Duration,
+    /// How long to wait for the connection to the server.
+    pub connect_timeout: Option<Duration>,
+    /// How long a whole `translate_*` call can take, all chunks included.
+    pub total_timeout: Option<Duration>,
+    /// Delay between requests.
+    pub request_delay: Duration,
+    /// Proxy address, see [`crate::GoogleTranslator`] for the format.
+    pub proxy_address: Option<String>,
+    /// How many requests can be handled concurrently, without context chunks only.
+    ///
+    /// Unlimited in async by default. The sync API translates the chunks on up to
+    /// `max_concurrency` threads, one at a time if unset.
+    pub max_concurrency: Option<usize>,
+    /// Maximum number of chars of a chunk, keep it well under the context of the model.
+    pub text_limit: usize,
+    /// Headers added to every request.
+    pub headers: Vec<(String, String)>,
+    /// Root certificates and client identity, e.g. for a server with a private CA.
+    pub tls: Option<Arc<TlsConfig>>,
+    /// Sends the requests, reqwest by default (ureq without the `reqwest` feature).
+    #[builder(Into=!)]
+    pub transport: Arc<dyn Transport>,
+}
+
+impl translator::Translator for LlmTranslator {
+    #[cfg(feature = "async")]
+    async fn translate_async(
+        &self,
+        text: &str,
+        source_language: &str,
+        target_language: &str,
+    ) -> Result<String, translator::Error> {
+        validate_target(target_language, BACKEND)?;
+        let system_prompt = &self.system_prompt(source_language, target_language);
+        let config = self.request_config();
+        if self.context_chunks == 0 {
+            return self
+                .chunking()
+                .translate_async(text, |chunk| {
+                    send_async_request(system_prompt, &[], chunk, &config)
+                })
+                .await;
+        }
+        self.chunking()
+            .translate_in_context_async(text, self.context_chunks, |chunk, previous| async move {
+                send_async_request(system_prompt, &previous, chunk, &config).await
+            })
+            .await
+    }
+
+    fn translate_sync(
+        &self,
+        text: &str,
+        source_language: &str,
+        target_language: &str,
+    ) -> Result<String, translator::Error> {
+        validate_target(target_language, BACKEND)?;
+        let system_prompt = self.system_prompt(source_language, target_language);
+        let send = |chunk: &str, previous: &[(&str, String)], time_left: Option<Duration>| {
+            let mut config = self.request_config();
+            if let Some(time_left) = time_left {
+                config.connection.timeout = config.connection.timeout.min(time_left);
+            }
+            send_sync_request(&system_prompt, previous, chunk, &config)
+        };
+        if self.context_chunks == 0 {
+            return self
+                .chunking()
+                .translate_sync(text, |chunk, time_left| send(chunk, &[], time_left));
+        }
+        self.chunking()
+            .translate_in_context_sync(text, self.context_chunks, send)
+    }
+
+    /// The languages named in the prompt, the model may know many more.
+    fn supported_languages(&self) -> Vec<Language> {
+        shared::languages(languages::LANGUAGES)
+    }
+}
+
+impl LlmTranslator {
+    /// Builds a translator from string settings, missing keys keep the default values.
+    ///
+    /// Keys are the field names: `url`, `api_key`, `model`, `system_prompt`, `temperature`,
+    /// `context_chunks`, `request_timeout`, `connect_timeout`, `total_timeout`,
+    /// `request_delay` (in seconds), `proxy_address`, `max_concurrency` and `text_limit`.
+    pub fn from_config(config: &BackendConfig) -> Result<Self, translator::Error> {
+        let mut translator = LlmTranslator::default();
+        if let Some(url) = config.get("url") {
+            translator.url = url.to_string();
+        }
+        if let Some(api_key) = config.get("api_key") {
+            translator.api_key = Some(api_key.to_string());
+        }
+        if let Some(model) = config.get("model") {
+            translator.model = model.to_string();
+        }
+        if let Some(system_prompt) = config.get("system_prompt") {
+            translator.system_prompt = system_prompt.to_string();
+        }
+        if let Some(temperature) = config.parse("temperature")? {
+            translator.temperature = Some(temperature);
+        }
+        if let Some(context_chunks) = config.parse("context_chunks")? {
+            translator.context_chunks = context_chunks;
+        }
+        read_shared_config!(translator, config);
+
+        Ok(translator)
+    }
+
+    /// The system prompt with the names of the languages.
+    fn system_prompt(&self, source_language: &str, target_language: &str) -> String {
+        let source_language = match source_language {
+            "" | "auto" => "the language of the text",
+            source_language => language_name(source_language),
+        };
+        self.system_prompt
+            .replace("{source}", source_language)
+            .replace("{target}", language_name(target_language))
+    }
+
+    fn chunking(&self) -> Chunking {
+        chunking!(self, BACKEND)
+    }
+
+    fn request_config(&self) -> RequestConfig<'_> {
+        RequestConfig {
+            connection: connection!(self),
+            url: &self.url,
+            api_key: self.api_key.as_deref(),
+            model: &self.model,
+            temperature: self.temperature,
+        }
+    }
+}
+
+impl Default for LlmTranslator {
+    fn default() -> Self {
+        LlmTranslator {
+            url: "http://localhost:8080/v1".to_string(),
+            api_key: None,
+            model: String::new(),
+            system_prompt: DEFAULT_SYSTEM_PROMPT.to_string(),
+            temperature: Some(0.0),
+            context_chunks: 2,
+            request_timeout: Duration::from_secs(120),
+            connect_timeout: None,
+            total_timeout: None,
+            request_delay: Duration::ZERO,
+            proxy_address: None,
+            max_concurrency: None,
+            text_limit: 2000,
+            headers: Vec::new(),
+            tls: None,
+            transport: transport::default_transport(),
+        }
+    }
+}
+
+/// Name of the translator in the errors.
+const BACKEND: &str = "llm";
+const DEFAULT_SYSTEM_PROMPT: &str = "You are a professional translator. Translate the \
+    messages of the user from {source} to {target}. Answer with the translation only, \
+    without notes, quotes or explanations, and keep the formatting of the text.";
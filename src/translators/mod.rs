@@ -1,3 +1,43 @@
+#[cfg(feature = "bing")]
+pub mod bing;
+#[cfg(any(
+    feature = "google",
+    feature = "libre",
+    feature = "deepl",
+    feature = "bing",
+    feature = "yandex",
+    feature = "mymemory",
+    feature = "lingva",
+    feature = "llm",
+    feature = "fallback"
+))]
+pub(crate) mod chunks;
+pub mod circuit;
+pub mod concurrency;
+#[cfg(feature = "deepl")]
+pub mod deepl;
+pub mod detector;
+#[cfg(feature = "fallback")]
+pub mod fallback;
 #[cfg(feature = "google")]
 pub mod google;
+#[cfg(feature = "async")]
+pub mod hedging;
+#[cfg(feature = "libre")]
+pub mod libre;
+#[cfg(feature = "lingva")]
+pub mod lingva;
+#[cfg(feature = "llm")]
+pub mod llm;
+#[cfg(feature = "mymemory")]
+pub mod mymemory;
+pub mod registry;
+pub mod rotation;
+#[cfg(feature = "async")]
+pub(crate) mod runtime;
+#[cfg(feature = "google")]
+pub(crate) mod skip;
 pub mod translator;
+pub mod transport;
+#[cfg(feature = "yandex")]
+pub mod yandex;
@@ -0,0 +1,122 @@
+use crate::translators::translator::shared::{self, Connection};
+use crate::translators::translator::{self, ErrorKind};
+use crate::translators::transport::{Request, Response};
+use serde_json::Value;
+use urlencoding::encode;
+
+/// Settings of a single request.
+#[derive(Debug, Clone, Copy)]
+pub struct RequestConfig<'a> {
+    pub connection: Connection<'a>,
+    /// Url of the API, without the endpoint.
+    pub url: &'a str,
+    /// Sent as `de`, raises the daily quota.
+    pub email: Option<&'a str>,
+    pub api_key: Option<&'a str>,
+}
+
+impl RequestConfig<'_> {
+    fn get(&self, target_language: &str, source_language: &str, text: &str) -> Request {
+        let mut url = format!(
+            "{}/get?q={}&langpair={}",
+            self.url.trim_end_matches('/'),
+            encode(text),
+            encode(&lang_pair(target_language, source_language))
+        );
+        if let Some(email) = self.email {
+            url.push_str(&format!("&de={}", encode(email)));
+        }
+        if let Some(api_key) = self.api_key {
+            url.push_str(&format!("&key={}", encode(api_key)));
+        }
+        self.connection
+            .request(Request::get(url))
+            .header("Accept", "application/json")
+    }
+}
+
+#[cfg(feature = "async")]
+pub async fn send_async_request(
+    target_language: &str,
+    source_language: &str,
+    text: &str,
+    config: &RequestConfig<'_>,
+) -> Result<String, translator::Error> {
+    let request = config.get(target_language, source_language, text);
+    let response = config.connection.transport.send_async(&request).await?;
+
+    get_translated_text(&check_response(response)?)
+}
+
+pub fn send_sync_request(
+    target_language: &str,
+    source_language: &str,
+    text: &str,
+    config: &RequestConfig<'_>,
+) -> Result<String, translator::Error> {
+    let request = config.get(target_language, source_language, text);
+    let response = config.connection.transport.send_sync(&request)?;
+
+    get_translated_text(&check_response(response)?)
+}
+
+/// `"en|fr"`, MyMemory uses `"autodetect"` for the detection of the source language.
+fn lang_pair(target_language: &str, source_language: &str) -> String {
+    let source_language = match source_language {
+        "" | "auto" => "autodetect",
+        source_language => source_language,
+    };
+    format!("{}|{}", source_language, target_language)
+}
+
+/// MyMemory answers the errors with `responseStatus` and `responseDetails`, often with
+/// a 200. `responseStatus` is a number or a string.
+fn check_response(response: Response) -> Result<Value, translator::Error> {
+    let value: Option<Value> = serde_json::from_str(&response.body).ok();
+    let code = value
+        .as_ref()
+        .and_then(|value| value.get("responseStatus"))
+        .and_then(|status| match status {
+            Value::String(status) => status.parse().ok(),
+            status => status
+                .as_u64()
+                .and_then(|status| u16::try_from(status).ok()),
+        })
+        .unwrap_or(response.status);
+    if (200..300).contains(&response.status) && (200..300).contains(&code) {
+        return value.ok_or_else(|| translator::Error::new(ErrorKind::DecodeBody, response.body));
+    }
+
+    let quota_finished = value
+        .as_ref()
+        .and_then(|value| value.get("quotaFinished"))
+        .and_then(Value::as_bool)
+        == Some(true);
+    let message = value
+        .as_ref()
+        .and_then(|value| value.get("responseDetails"))
+        .and_then(Value::as_str)
+        .filter(|message| !message.is_empty())
+        .map_or_else(|| response.url.clone(), str::to_string);
+    let kind = match code {
+        // "MYMEMORY WARNING: YOU USED ALL AVAILABLE FREE TRANSLATIONS FOR TODAY..."
+        _ if quota_finished || message.contains("ALL AVAILABLE FREE TRANSLATIONS") => {
+            ErrorKind::QuotaExceeded
+        }
+        // "INVALID LANGUAGE PAIR SPECIFIED...", "QUERY LENGTH LIMIT EXCEEDED..."
+        400 | 403 => ErrorKind::InvalidRequest,
+        429 => shared::rate_limited(&response),
+        _ => ErrorKind::Status,
+    };
+    Err(translator::Error::new(kind, message).with_status(code))
+}
+
+fn get_translated_text(value: &Value) -> Result<String, translator::Error> {
+    // {"responseData": {"translatedText": "Bonjour", "match": 1}, "responseStatus": 200, ...}
+    value
+        .get("responseData")
+        .and_then(|data| data.get("translatedText"))
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .ok_or_else(|| translator::Error::new(ErrorKind::DecodeBody, value.to_string()))
+}
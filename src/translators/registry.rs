@@ -0,0 +1,215 @@
+use crate::translators::translator::{DynTranslator, Error, ErrorKind};
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Builds a translator from its config.
+pub type TranslatorFactory =
+    Arc<dyn Fn(&BackendConfig) -> Result<Box<dyn DynTranslator>, Error> + Send + Sync>;
+
+/// Builds translators by name from a config, to choose a backend at runtime.
+///
+/// [`TranslatorRegistry::default`] contains the translators of the enabled features
+/// (`"google"`, `"libre"`, `"deepl"`, `"bing"`, `"yandex"`, `"mymemory"`, `"lingva"`,
+/// `"llm"`), custom ones can be added with [`TranslatorRegistry::register`].
+///
+/// ``` ignore
+/// use translators::{BackendConfig, TranslatorRegistry};
+///
+/// let config = BackendConfig::new()
+///     .set("request_timeout", "10")
+///     .set("proxy_address", "socks5://0.0.0.0:8080");
+/// let translator = TranslatorRegistry::default().build("google", &config).unwrap();
+/// let translated_text = translator
+///     .translate_sync_dyn("Hello, world!", "en", "fr")
+///     .unwrap();
+/// ```
+#[derive(Clone)]
+pub struct TranslatorRegistry {
+    factories: HashMap<String, TranslatorFactory>,
+}
+
+impl TranslatorRegistry {
+    /// Registry without any translator.
+    pub fn empty() -> Self {
+        TranslatorRegistry {
+            factories: HashMap::new(),
+        }
+    }
+
+    /// Adds a translator, replaces the one with the same name.
+    pub fn register<F>(&mut self, name: &str, factory: F) -> &mut Self
+    where
+        F: Fn(&BackendConfig) -> Result<Box<dyn DynTranslator>, Error> + Send + Sync + 'static,
+    {
+        self.factories.insert(name.to_string(), Arc::new(factory));
+        self
+    }
+
+    pub fn build(
+        &self,
+        name: &str,
+        config: &BackendConfig,
+    ) -> Result<Box<dyn DynTranslator>, Error> {
+        match self.factories.get(name) {
+            Some(factory) => factory(config),
+            None => Err(Error::new(
+                ErrorKind::Builder,
+                format!("unknown translator: {}", name),
+            )),
+        }
+    }
+
+    /// Names of the registered translators.
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.factories.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+}
+
+impl Default for TranslatorRegistry {
+    fn default() -> Self {
+        #[allow(unused_mut)]
+        let mut registry = TranslatorRegistry::empty();
+        #[cfg(feature = "google")]
+        registry.register("google", |config| {
+            let translator = crate::GoogleTranslator::from_config(config)?;
+            Ok(Box::new(translator))
+        });
+        #[cfg(feature = "bing")]
+        registry.register("bing", |config| {
+            let translator = crate::BingTranslator::from_config(config)?;
+            Ok(Box::new(translator))
+        });
+        #[cfg(feature = "deepl")]
+        registry.register("deepl", |config| {
+            let translator = crate::DeepLTranslator::from_config(config)?;
+            Ok(Box::new(translator))
+        });
+        #[cfg(feature = "libre")]
+        registry.register("libre", |config| {
+            let translator = crate::LibreTranslator::from_config(config)?;
+            Ok(Box::new(translator))
+        });
+        #[cfg(feature = "lingva")]
+        registry.register("lingva", |config| {
+            let translator = crate::LingvaTranslator::from_config(config)?;
+            Ok(Box::new(translator))
+        });
+        #[cfg(feature = "llm")]
+        registry.register("llm", |config| {
+            let translator = crate::LlmTranslator::from_config(config)?;
+            Ok(Box::new(translator))
+        });
+        #[cfg(feature = "mymemory")]
+        registry.register("mymemory", |config| {
+            let translator = crate::MyMemoryTranslator::from_config(config)?;
+            Ok(Box::new(translator))
+        });
+        #[cfg(feature = "yandex")]
+        registry.register("yandex", |config| {
+            let translator = crate::YandexTranslator::from_config(config)?;
+            Ok(Box::new(translator))
+        });
+        registry
+    }
+}
+
+impl fmt::Debug for TranslatorRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("TranslatorRegistry")
+            .field("names", &self.names())
+            .finish()
+    }
+}
+
+/// Settings of a translator as strings, e.g. read from a config file.
+///
+/// Durations are in seconds and can be fractional, e.g. `"0.5"`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BackendConfig {
+    values: HashMap<String, String>,
+}
+
+impl BackendConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set(mut self, key: &str, value: impl Into<String>) -> Self {
+        self.values.insert(key.to_string(), value.into());
+        self
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.values.get(key).map(String::as_str)
+    }
+
+    /// Parses a value, `None` if the key is missing.
+    pub fn parse<T: FromStr>(&self, key: &str) -> Result<Option<T>, Error>
+    where
+        T::Err: fmt::Display,
+    {
+        self.get(key)
+            .map(|value| {
+                value.trim().parse().map_err(|e| {
+                    Error::new(
+                        ErrorKind::Builder,
+                        format!("invalid value {:?} for {}: {}", value, key, e),
+                    )
+                })
+            })
+            .transpose()
+    }
+
+    /// Parses a list of values separated by commas, `None` if the key is missing.
+    pub fn list<T: FromStr>(&self, key: &str) -> Result<Option<Vec<T>>, Error>
+    where
+        T::Err: fmt::Display,
+    {
+        self.get(key)
+            .map(|values| {
+                values
+                    .split(',')
+                    .filter(|value| !value.trim().is_empty())
+                    .map(|value| {
+                        value.trim().parse().map_err(|e| {
+                            Error::new(
+                                ErrorKind::Builder,
+                                format!("invalid value {:?} for {}: {}", value, key, e),
+                            )
+                        })
+                    })
+                    .collect()
+            })
+            .transpose()
+    }
+
+    /// Parses a duration in seconds, `None` if the key is missing.
+    pub fn duration(&self, key: &str) -> Result<Option<Duration>, Error> {
+        self.parse::<f64>(key)?
+            .map(|secs| {
+                Duration::try_from_secs_f64(secs).map_err(|e| {
+                    Error::new(
+                        ErrorKind::Builder,
+                        format!("invalid duration {} for {}: {}", secs, key, e),
+                    )
+                })
+            })
+            .transpose()
+    }
+}
+
+impl<K: Into<String>, V: Into<String>> FromIterator<(K, V)> for BackendConfig {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        BackendConfig {
+            values: iter
+                .into_iter()
+                .map(|(key, value)| (key.into(), value.into()))
+                .collect(),
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Items used in turn, one per request, e.g. the local addresses of a translator.
+///
+/// An item can be quarantined, when Google answers with a captcha: it is skipped
+/// for the quarantine duration, unless all the items are quarantined. Clones share
+/// the rotation and the quarantines.
+///
+/// ```ignore
+/// let pool = RotationPool::new(vec![
+///     "192.0.2.10".parse::<IpAddr>().unwrap(),
+///     "192.0.2.11".parse().unwrap(),
+/// ])
+/// .with_quarantine(Duration::from_secs(600));
+/// ```
+#[derive(Clone)]
+pub struct RotationPool<T> {
+    inner: Arc<Inner<T>>,
+}
+
+struct Inner<T> {
+    items: Vec<T>,
+    quarantine: Duration,
+    next: AtomicUsize,
+    /// End of the quarantine of each item.
+    quarantined_until: Mutex<Vec<Option<Instant>>>,
+}
+
+impl<T: Clone + PartialEq> RotationPool<T> {
+    /// Pool with a quarantine of 5 minutes.
+    pub fn new(items: Vec<T>) -> Self {
+        let quarantined_until = Mutex::new(vec![None; items.len()]);
+        RotationPool {
+            inner: Arc::new(Inner {
+                items,
+                quarantine: Duration::from_secs(300),
+                next: AtomicUsize::new(0),
+                quarantined_until,
+            }),
+        }
+    }
+
+    /// Same items with another quarantine duration, the quarantines are reset.
+    pub fn with_quarantine(self, quarantine: Duration) -> Self {
+        let items = self.inner.items.clone();
+        let mut pool = RotationPool::new(items);
+        Arc::get_mut(&mut pool.inner)
+            .expect("the new pool isn't shared")
+            .quarantine = quarantine;
+        pool
+    }
+
+    /// Next item not in quarantine, or the one leaving it first.
+    ///
+    /// `None` only for an empty pool.
+    pub fn next(&self) -> Option<T> {
+        let len = self.inner.items.len();
+        if len == 0 {
+            return None;
+        }
+        let now = Instant::now();
+        let quarantined_until = self.inner.quarantined_until.lock().unwrap();
+        let start = self.inner.next.fetch_add(1, Ordering::Relaxed);
+        let index = (start..start + len)
+            .map(|index| index % len)
+            .find(|&index| quarantined_until[index].is_none_or(|until| until <= now))
+            .unwrap_or_else(|| {
+                (0..len)
+                    .min_by_key(|&index| quarantined_until[index])
+                    .unwrap_or(0)
+            });
+
+        Some(self.inner.items[index].clone())
+    }
+
+    /// Skips the item for the quarantine duration.
+    pub fn quarantine(&self, item: &T) {
+        let until = Instant::now() + self.inner.quarantine;
+        let mut quarantined_until = self.inner.quarantined_until.lock().unwrap();
+        for (index, _) in self
+            .inner
+            .items
+            .iter()
+            .enumerate()
+            .filter(|(_, other)| *other == item)
+        {
+            quarantined_until[index] = Some(until);
+        }
+    }
+
+    /// Number of items not in quarantine.
+    pub fn available(&self) -> usize {
+        let now = Instant::now();
+        self.inner
+            .quarantined_until
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|until| until.is_none_or(|until| until <= now))
+            .count()
+    }
+
+    pub fn items(&self) -> &[T] {
+        &self.inner.items
+    }
+}
+
+impl<T: Clone + PartialEq> From<Vec<T>> for RotationPool<T> {
+    fn from(items: Vec<T>) -> Self {
+        RotationPool::new(items)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for RotationPool<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RotationPool")
+            .field("items", &self.inner.items)
+            .field("quarantine", &self.inner.quarantine)
+            .finish()
+    }
+}
@@ -0,0 +1,35 @@
+//! Timers and blocking tasks for async translators.
+//!
+//! The functions work on any executor (smol, async-std, `futures::executor`, ...),
+//! the `tokio-async` feature only moves the reqwest requests to tokio.
+
+use futures::future::{self, Either};
+use std::future::Future;
+use std::time::Duration;
+
+pub(crate) async fn sleep(duration: Duration) {
+    futures_timer::Delay::new(duration).await;
+}
+
+/// Returns `None` if the future isn't finished after `duration`.
+pub(crate) async fn timeout<F: Future>(duration: Duration, future: F) -> Option<F::Output> {
+    let future = std::pin::pin!(future);
+    let sleep = std::pin::pin!(sleep(duration));
+    match future::select(future, sleep).await {
+        Either::Left((output, _)) => Some(output),
+        Either::Right(_) => None,
+    }
+}
+
+/// Runs a blocking function on a bounded pool of threads.
+#[cfg(any(
+    feature = "ureq",
+    all(feature = "reqwest", not(feature = "tokio-async"))
+))]
+pub(crate) async fn unblock<T, F>(function: F) -> T
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    blocking::unblock(function).await
+}
@@ -0,0 +1,79 @@
+#[cfg(feature = "offline-detect")]
+use crate::translators::detector::{offline::OfflineDetector, LanguageDetector};
+use regex::Regex;
+
+/// Minimal confidence of the offline detection to keep a chunk as is.
+#[cfg(feature = "offline-detect")]
+const MIN_CONFIDENCE: f64 = 0.9;
+
+/// Symbols that make up a noticeable part of source code.
+const CODE_SYMBOLS: &[char] = &['{', '}', '[', ']', '(', ')', ';', '=', '<', '>'];
+/// Non-whitespace characters needed before the density of symbols is trusted.
+const MIN_CODE_LENGTH: usize = 10;
+
+/// Whether the text has nothing to translate: whitespace, numbers, URLs or a code block.
+pub(crate) fn is_untranslatable(text: &str) -> bool {
+    if is_indented_block(text) {
+        return true;
+    }
+    let text = text.trim();
+    if text.is_empty() || !text.chars().any(char::is_alphabetic) {
+        return true;
+    }
+
+    // urls and emails separated by whitespace
+    let url =
+        Regex::new(r"^(?:[a-zA-Z][a-zA-Z0-9+.-]*://|www\.)\S+$|^[^\s@]+@[^\s@]+\.\S+$").unwrap();
+    if text.split_whitespace().all(|word| url.is_match(word)) {
+        return true;
+    }
+
+    is_fenced_block(text)
+}
+
+/// A single fenced code block, `"```a``` prose ```b```"` has prose between two blocks.
+fn is_fenced_block(text: &str) -> bool {
+    text.strip_prefix("```")
+        .and_then(|text| text.strip_suffix("```"))
+        .is_some_and(|code| !code.contains("```"))
+}
+
+/// Markdown code block: several lines, all of them indented by a tab or 4 spaces.
+fn is_indented_block(text: &str) -> bool {
+    let mut lines = text.lines().filter(|line| !line.trim().is_empty());
+    let several = lines.clone().nth(1).is_some();
+    several && lines.all(|line| line.starts_with("    ") || line.starts_with('\t'))
+}
+
+/// Long enough, one symbol out of five characters, and a statement or a block:
+/// parentheses alone are common in prose.
+///
+/// Only a guess, prose full of formulas looks the same.
+pub(crate) fn is_dense_code(text: &str) -> bool {
+    let (mut symbols, mut total) = (0, 0);
+    for char in text.chars().filter(|char| !char.is_whitespace()) {
+        total += 1;
+        if CODE_SYMBOLS.contains(&char) {
+            symbols += 1;
+        }
+    }
+    let statement = text.contains(';') || text.contains('=');
+    let block = text.contains('{') && text.contains('}');
+    total >= MIN_CODE_LENGTH && symbols * 5 >= total && (statement || block)
+}
+
+/// Language of the text when the offline detector is confident about it.
+#[cfg(feature = "offline-detect")]
+pub(crate) fn confident_language(text: &str) -> Option<String> {
+    OfflineDetector
+        .detect_sync(text)
+        .ok()
+        .filter(|detection| detection.confidence >= MIN_CONFIDENCE)
+        .map(|detection| detection.language)
+}
+
+/// Without the `offline-detect` feature the language is left to the translator.
+#[cfg(not(feature = "offline-detect"))]
+pub(crate) fn confident_language(_text: &str) -> Option<String> {
+    None
+}
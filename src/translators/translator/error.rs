@@ -0,0 +1,278 @@
+use std::error::Error as StdError;
+use std::fmt;
+use std::ops::Range;
+use std::str::Utf8Error;
+use std::sync::Arc;
+use std::time::Duration;
+#[cfg(feature = "tokio-async")]
+use tokio::task::JoinError;
+
+/// Error of a translator.
+///
+/// Use [`Error::kind`] to know what happened and [`Error::is_retryable`]
+/// to know if the same request can succeed later.
+#[derive(Debug, Clone)]
+pub struct Error {
+    inner: Box<Inner>,
+}
+
+#[derive(Debug, Clone)]
+struct Inner {
+    kind: ErrorKind,
+    message: String,
+    status: Option<u16>,
+    source: Option<Arc<dyn StdError + Send + Sync>>,
+    chunk_index: Option<usize>,
+    chunk_range: Option<Range<usize>>,
+    backend: Option<&'static str>,
+}
+
+/// Kind of an [`Error`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    Builder,
+    Redirect,
+    /// Unsuccessful HTTP status, see [`Error::status`].
+    Status,
+    Timeout,
+    ConnectFailed,
+    DecodeBody,
+    Encoding,
+    Captcha,
+    /// Too many requests, `retry_after` is the delay asked by the server.
+    RateLimited {
+        retry_after: Option<Duration>,
+    },
+    InvalidRequest,
+    /// The character quota of the account is used up.
+    QuotaExceeded,
+    /// The requests are refused after too many failures, `retry_after` is the time left
+    /// before a new try, see [`crate::CircuitBreaker`].
+    CircuitOpen {
+        retry_after: Option<Duration>,
+    },
+    LanguageDetection,
+    UnsupportedLanguage,
+    JoinTask,
+    Unknown,
+}
+
+impl Error {
+    pub fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+        Error {
+            inner: Box::new(Inner {
+                kind,
+                message: message.into(),
+                status: None,
+                source: None,
+                chunk_index: None,
+                chunk_range: None,
+                backend: None,
+            }),
+        }
+    }
+
+    /// Sets the HTTP status code of the response.
+    pub fn with_status(mut self, status: u16) -> Self {
+        self.inner.status = Some(status);
+        self
+    }
+
+    /// Sets the underlying error.
+    pub fn with_source(mut self, source: impl StdError + Send + Sync + 'static) -> Self {
+        self.inner.source = Some(Arc::new(source));
+        self
+    }
+
+    /// Sets the chunk that failed, `range` is in bytes of the original text.
+    pub fn with_chunk(mut self, index: usize, range: Range<usize>) -> Self {
+        self.inner.chunk_index = Some(index);
+        self.inner.chunk_range = Some(range);
+        self
+    }
+
+    /// Sets the name of the translator, e.g. `"google"`.
+    pub fn with_backend(mut self, backend: &'static str) -> Self {
+        self.inner.backend = Some(backend);
+        self
+    }
+
+    pub fn kind(&self) -> &ErrorKind {
+        &self.inner.kind
+    }
+
+    pub fn message(&self) -> &str {
+        &self.inner.message
+    }
+
+    /// HTTP status code, if the error comes from a response.
+    pub fn status(&self) -> Option<u16> {
+        self.inner.status
+    }
+
+    /// Index of the chunk that failed.
+    pub fn chunk_index(&self) -> Option<usize> {
+        self.inner.chunk_index
+    }
+
+    /// Byte range of the chunk that failed in the original text.
+    pub fn chunk_range(&self) -> Option<Range<usize>> {
+        self.inner.chunk_range.clone()
+    }
+
+    /// Name of the translator that failed, e.g. `"google"`.
+    pub fn backend(&self) -> Option<&'static str> {
+        self.inner.backend
+    }
+
+    /// Whether the same request can succeed later or through another proxy.
+    pub fn is_retryable(&self) -> bool {
+        match self.inner.kind {
+            ErrorKind::Timeout
+            | ErrorKind::ConnectFailed
+            | ErrorKind::Captcha
+            | ErrorKind::RateLimited { .. }
+            | ErrorKind::CircuitOpen { .. } => true,
+            ErrorKind::Status => self.inner.status.is_some_and(|status| status >= 500),
+            _ => false,
+        }
+    }
+}
+
+impl StdError for Error {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        self.inner
+            .source
+            .as_deref()
+            .map(|source| source as &(dyn StdError + 'static))
+    }
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErrorKind::Builder => write!(f, "Builder error"),
+            ErrorKind::Redirect => write!(f, "Redirect error"),
+            ErrorKind::Status => write!(f, "Status error"),
+            ErrorKind::Timeout => write!(f, "Timeout error"),
+            ErrorKind::ConnectFailed => write!(f, "ConnectFailed error"),
+            ErrorKind::DecodeBody => write!(f, "Body decoding error"),
+            ErrorKind::Encoding => write!(f, "Encoding error"),
+            ErrorKind::Captcha => write!(f, "Captcha"),
+            ErrorKind::RateLimited {
+                retry_after: Some(retry_after),
+            } => write!(f, "Rate limited, retry after {:?}", retry_after),
+            ErrorKind::RateLimited { retry_after: None } => write!(f, "Rate limited"),
+            ErrorKind::InvalidRequest => write!(f, "Invalid request"),
+            ErrorKind::QuotaExceeded => write!(f, "Quota exceeded"),
+            ErrorKind::CircuitOpen {
+                retry_after: Some(retry_after),
+            } => write!(f, "Circuit open, retry after {:?}", retry_after),
+            ErrorKind::CircuitOpen { retry_after: None } => write!(f, "Circuit open"),
+            ErrorKind::LanguageDetection => write!(f, "Language detection error"),
+            ErrorKind::UnsupportedLanguage => write!(f, "Unsupported language"),
+            ErrorKind::JoinTask => write!(f, "Join task error"),
+            ErrorKind::Unknown => write!(f, "Unknown error"),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(backend) = self.inner.backend {
+            write!(f, "{}: ", backend)?;
+        }
+        write!(f, "{}", self.inner.kind)?;
+        if let Some(status) = self.inner.status {
+            write!(f, " (status {})", status)?;
+        }
+        if !self.inner.message.is_empty() {
+            write!(f, ": {}", self.inner.message)?;
+        }
+        if let (Some(index), Some(range)) = (self.inner.chunk_index, &self.inner.chunk_range) {
+            write!(f, " [chunk {}, bytes {:?}]", index, range)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "reqwest")]
+impl From<reqwest::Error> for Error {
+    fn from(e: reqwest::Error) -> Self {
+        let kind = if e.is_connect() {
+            ErrorKind::ConnectFailed
+        } else if e.is_timeout() {
+            ErrorKind::Timeout
+        } else if e.is_builder() {
+            ErrorKind::Builder
+        } else if e.is_redirect() {
+            ErrorKind::Redirect
+        } else if e.is_status() {
+            ErrorKind::Status
+        } else if e.is_request() {
+            ErrorKind::InvalidRequest
+        } else if e.is_decode() {
+            ErrorKind::DecodeBody
+        } else {
+            ErrorKind::Unknown
+        };
+        let mut error = Error::new(kind, e.to_string());
+        if let Some(status) = e.status() {
+            error = error.with_status(status.as_u16());
+        }
+        error.with_source(e)
+    }
+}
+
+#[cfg(feature = "ureq")]
+impl From<ureq::Error> for Error {
+    fn from(e: ureq::Error) -> Self {
+        use ureq::ErrorKind as UreqKind;
+
+        let kind = match e.kind() {
+            UreqKind::Dns | UreqKind::ConnectionFailed | UreqKind::ProxyConnect => {
+                ErrorKind::ConnectFailed
+            }
+            UreqKind::Io if is_io_timeout(&e) => ErrorKind::Timeout,
+            UreqKind::InvalidUrl
+            | UreqKind::UnknownScheme
+            | UreqKind::InvalidProxyUrl
+            | UreqKind::InsecureRequestHttpsOnly => ErrorKind::Builder,
+            UreqKind::TooManyRedirects => ErrorKind::Redirect,
+            UreqKind::HTTP | UreqKind::ProxyUnauthorized => ErrorKind::Status,
+            UreqKind::BadStatus | UreqKind::BadHeader => ErrorKind::DecodeBody,
+            _ => ErrorKind::Unknown,
+        };
+        let mut error = Error::new(kind, e.to_string());
+        if let ureq::Error::Status(status, _) = e {
+            error = error.with_status(status);
+        }
+        error.with_source(e)
+    }
+}
+
+#[cfg(feature = "ureq")]
+fn is_io_timeout(e: &ureq::Error) -> bool {
+    e.source()
+        .and_then(|source| source.downcast_ref::<std::io::Error>())
+        .is_some_and(|e| {
+            matches!(
+                e.kind(),
+                std::io::ErrorKind::TimedOut | std::io::ErrorKind::WouldBlock
+            )
+        })
+}
+
+impl From<Utf8Error> for Error {
+    fn from(e: Utf8Error) -> Self {
+        Error::new(ErrorKind::Encoding, e.to_string()).with_source(e)
+    }
+}
+
+#[cfg(feature = "tokio-async")]
+impl From<JoinError> for Error {
+    fn from(e: JoinError) -> Self {
+        Error::new(ErrorKind::JoinTask, e.to_string()).with_source(e)
+    }
+}
@@ -1,95 +1,164 @@
-use std::fmt;
+mod error;
+#[cfg(any(
+    feature = "google",
+    feature = "libre",
+    feature = "deepl",
+    feature = "bing",
+    feature = "yandex",
+    feature = "mymemory",
+    feature = "lingva",
+    feature = "llm"
+))]
+pub(crate) mod shared;
+
+pub use error::{Error, ErrorKind};
 use std::fmt::Debug;
-use std::str::Utf8Error;
-#[cfg(feature = "tokio-async")]
-use tokio::task::JoinError;
+#[cfg(feature = "async")]
+use std::future::Future;
+#[cfg(feature = "async")]
+use std::pin::Pin;
 
 pub trait Translator: Clone + Default + Debug + Send + Sync {
-    #[cfg(feature = "tokio-async")]
-    async fn translate_async(
+    /// The future is `Send`, so generic code can run it on a multi-threaded runtime.
+    /// Use [`Translator::translate_async_owned`] for `tokio::spawn`.
+    #[cfg(feature = "async")]
+    fn translate_async(
         &self,
         text: &str,
         target_language: &str,
         source_language: &str,
-    ) -> Result<String, Error>;
+    ) -> impl Future<Output = Result<String, Error>> + Send;
+
+    /// Same as [`Translator::translate_async`] but the future doesn't borrow anything,
+    /// it can be passed to `tokio::spawn`:
+    ///
+    /// ``` ignore
+    /// fn spawn_translation<T: Translator + 'static>(
+    ///     translator: &T,
+    ///     text: String,
+    /// ) -> tokio::task::JoinHandle<Result<String, Error>> {
+    ///     tokio::spawn(translator.translate_async_owned(text, "en".into(), "fr".into()))
+    /// }
+    /// ```
+    #[cfg(feature = "async")]
+    fn translate_async_owned(
+        &self,
+        text: String,
+        source_language: String,
+        target_language: String,
+    ) -> impl Future<Output = Result<String, Error>> + Send + 'static
+    where
+        Self: 'static,
+    {
+        let translator = self.clone();
+        async move {
+            translator
+                .translate_async(&text, &source_language, &target_language)
+                .await
+        }
+    }
 
+    /// Blocks the current thread, also for the delay between requests.
+    /// It works inside an async runtime, but there prefer
+    /// [`Translator::translate_async`] or [`Translator::translate_blocking_in_async`].
     fn translate_sync(
         &self,
         text: &str,
         target_language: &str,
         source_language: &str,
     ) -> Result<String, Error>;
-}
 
-// error
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub enum Error {
-    Builder(String),
-    Redirect(String),
-    Status(String),
-    Timeout(String),
-    ConnectFailed(String),
-    DecodeBody(String),
-    Encoding(String),
-    Captcha(String),
-    InvalidRequest(String),
+    /// Runs [`Translator::translate_sync`] with `tokio::task::spawn_blocking`,
+    /// so the blocking requests and delays don't stall the executor threads.
     #[cfg(feature = "tokio-async")]
-    JoinTask(String),
-    Uknown(String),
+    fn translate_blocking_in_async(
+        &self,
+        text: String,
+        source_language: String,
+        target_language: String,
+    ) -> impl Future<Output = Result<String, Error>> + Send + 'static
+    where
+        Self: 'static,
+    {
+        let translator = self.clone();
+        async move {
+            tokio::task::spawn_blocking(move || {
+                translator.translate_sync(&text, &source_language, &target_language)
+            })
+            .await?
+        }
+    }
+
+    /// Languages that can be used as source or target.
+    fn supported_languages(&self) -> Vec<Language>;
 }
 
-impl std::error::Error for Error {}
+/// Boxed future returned by [`DynTranslator`].
+#[cfg(feature = "async")]
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
 
-impl fmt::Display for Error {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self {
-            Error::Builder(ref e) => write!(f, "Builder error: {}", e),
-            Error::Redirect(ref e) => write!(f, "Redirect error: {}", e),
-            Error::Status(ref e) => write!(f, "Status error: {}", e),
-            Error::Timeout(ref e) => write!(f, "Timeout error: {}", e),
-            Error::ConnectFailed(ref e) => write!(f, "ConnectFailed error: {}", e),
-            Error::DecodeBody(ref e) => write!(f, "Body decoding error: {}", e),
-            Error::Captcha(ref e) => write!(f, "Captcha: {}", e),
-            Error::Encoding(ref e) => write!(f, "Encoding error: {}", e),
-            Error::InvalidRequest(ref e) => write!(f, "Invalid request: {}", e),
-            #[cfg(feature = "tokio-async")]
-            Error::JoinTask(ref e) => write!(f, "Tokio join task error: {}", e),
-            Error::Uknown(ref e) => write!(f, "Unknown error: {}", e),
-        }
-    }
+/// Object-safe version of [`Translator`], usable as `Box<dyn DynTranslator>`
+/// or `Arc<dyn DynTranslator>` to pick a translator at runtime.
+///
+/// Implemented for every [`Translator`]:
+///
+/// ``` ignore
+/// use translators::{DynTranslator, GoogleTranslator};
+///
+/// let translator: Box<dyn DynTranslator> = Box::new(GoogleTranslator::default());
+/// let translated_text = translator
+///     .translate_sync_dyn("Hello, world!", "en", "fr")
+///     .unwrap();
+/// ```
+pub trait DynTranslator: Debug + Send + Sync {
+    #[cfg(feature = "async")]
+    fn translate_async_dyn<'a>(
+        &'a self,
+        text: &'a str,
+        source_language: &'a str,
+        target_language: &'a str,
+    ) -> BoxFuture<'a, Result<String, Error>>;
+
+    fn translate_sync_dyn(
+        &self,
+        text: &str,
+        source_language: &str,
+        target_language: &str,
+    ) -> Result<String, Error>;
+
+    fn supported_languages_dyn(&self) -> Vec<Language>;
 }
 
-impl From<reqwest::Error> for Error {
-    fn from(e: reqwest::Error) -> Self {
-        if e.is_connect() {
-            Error::ConnectFailed(e.to_string())
-        } else if e.is_timeout() {
-            Error::Timeout(e.to_string())
-        } else if e.is_builder() {
-            Error::Builder(e.to_string())
-        } else if e.is_redirect() {
-            Error::Redirect(e.to_string())
-        } else if e.is_status() {
-            Error::Status(e.to_string())
-        } else if e.is_request() {
-            Error::InvalidRequest(e.to_string())
-        } else if e.is_decode() {
-            Error::DecodeBody(e.to_string())
-        } else {
-            Error::Uknown(e.to_string())
-        }
+impl<T: Translator> DynTranslator for T {
+    #[cfg(feature = "async")]
+    fn translate_async_dyn<'a>(
+        &'a self,
+        text: &'a str,
+        source_language: &'a str,
+        target_language: &'a str,
+    ) -> BoxFuture<'a, Result<String, Error>> {
+        Box::pin(self.translate_async(text, source_language, target_language))
     }
-}
 
-impl From<Utf8Error> for Error {
-    fn from(e: Utf8Error) -> Self {
-        Error::Encoding(e.to_string())
+    fn translate_sync_dyn(
+        &self,
+        text: &str,
+        source_language: &str,
+        target_language: &str,
+    ) -> Result<String, Error> {
+        self.translate_sync(text, source_language, target_language)
     }
-}
 
-#[cfg(feature = "tokio-async")]
-impl From<JoinError> for Error {
-    fn from(e: JoinError) -> Self {
-        Error::JoinTask(e.to_string())
+    fn supported_languages_dyn(&self) -> Vec<Language> {
+        self.supported_languages()
     }
 }
+
+/// Language supported by a translator.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Language {
+    /// Code to pass to the translator, e.g. `"en"`.
+    pub code: String,
+    /// Display name, e.g. `"English"`.
+    pub name: String,
+}
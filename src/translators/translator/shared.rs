@@ -0,0 +1,159 @@
+//! What the HTTP translators have in common: the settings, with the same field names in each
+//! translator, the languages and the rate limits.
+
+use crate::translators::translator::{ErrorKind, Language};
+use crate::translators::transport::Response;
+use std::time::Duration;
+
+#[cfg(any(
+    feature = "libre",
+    feature = "deepl",
+    feature = "bing",
+    feature = "yandex",
+    feature = "mymemory",
+    feature = "lingva",
+    feature = "llm"
+))]
+pub(crate) use self::open::{chunking, connection, validate_target, Connection};
+
+/// Languages of a `(code, name)` table.
+pub(crate) fn languages(table: &[(&str, &str)]) -> Vec<Language> {
+    table
+        .iter()
+        .map(|(code, name)| Language {
+            code: code.to_string(),
+            name: name.to_string(),
+        })
+        .collect()
+}
+
+/// Kind of a `429 Too Many Requests`, with the `Retry-After` in seconds if any.
+pub(crate) fn rate_limited(response: &Response) -> ErrorKind {
+    ErrorKind::RateLimited {
+        retry_after: response
+            .header("retry-after")
+            .and_then(|value| value.trim().parse().ok())
+            .map(Duration::from_secs),
+    }
+}
+
+/// Reads the keys of the shared settings into a translator, in a `from_config`.
+///
+/// The keys are the field names: `request_timeout`, `connect_timeout`, `total_timeout`,
+/// `request_delay` (in seconds), `proxy_address`, `max_concurrency` and `text_limit`.
+macro_rules! read_shared_config {
+    ($translator:ident, $config:ident) => {
+        if let Some(request_timeout) = $config.duration("request_timeout")? {
+            $translator.request_timeout = request_timeout;
+        }
+        if let Some(connect_timeout) = $config.duration("connect_timeout")? {
+            $translator.connect_timeout = Some(connect_timeout);
+        }
+        if let Some(total_timeout) = $config.duration("total_timeout")? {
+            $translator.total_timeout = Some(total_timeout);
+        }
+        if let Some(request_delay) = $config.duration("request_delay")? {
+            $translator.request_delay = request_delay;
+        }
+        if let Some(proxy_address) = $config.get("proxy_address") {
+            $translator.proxy_address = Some(proxy_address.to_string());
+        }
+        if let Some(max_concurrency) = $config.parse("max_concurrency")? {
+            $translator.max_concurrency = Some(max_concurrency);
+        }
+        if let Some(text_limit) = $config.parse("text_limit")? {
+            $translator.text_limit = text_limit;
+        }
+    };
+}
+pub(crate) use read_shared_config;
+
+/// The translators of open APIs, all but Google.
+#[cfg(any(
+    feature = "libre",
+    feature = "deepl",
+    feature = "bing",
+    feature = "yandex",
+    feature = "mymemory",
+    feature = "lingva",
+    feature = "llm"
+))]
+mod open {
+    use crate::translators::translator::{Error, ErrorKind};
+    use crate::translators::transport::{Request, TlsConfig, Transport};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    /// Settings of every request of a translator.
+    #[derive(Debug, Clone, Copy)]
+    pub(crate) struct Connection<'a> {
+        pub transport: &'a dyn Transport,
+        /// How long to wait for the whole request, from connect to the end of the body.
+        pub timeout: Duration,
+        /// How long to wait for the connection.
+        pub connect_timeout: Option<Duration>,
+        pub proxy_address: Option<&'a str>,
+        pub headers: &'a [(String, String)],
+        pub tls: Option<&'a Arc<TlsConfig>>,
+    }
+
+    impl Connection<'_> {
+        /// `request` with the headers of the translator and the connection settings.
+        pub(crate) fn request(&self, request: Request) -> Request {
+            Request {
+                headers: self.headers.to_vec(),
+                timeout: self.timeout,
+                connect_timeout: self.connect_timeout,
+                proxy_address: self.proxy_address.map(str::to_string),
+                tls: self.tls.cloned(),
+                ..request
+            }
+        }
+    }
+
+    /// These translators add languages over time, only the target is checked before a
+    /// request: it can't be one of the codes of the detection.
+    pub(crate) fn validate_target(
+        target_language: &str,
+        backend: &'static str,
+    ) -> Result<(), Error> {
+        if matches!(target_language.trim(), "" | "auto" | "auto-detect") {
+            return Err(
+                Error::new(ErrorKind::UnsupportedLanguage, target_language).with_backend(backend)
+            );
+        }
+
+        Ok(())
+    }
+
+    /// [`Connection`] of a translator from its `transport`, `request_timeout`,
+    /// `connect_timeout`, `proxy_address`, `headers` and `tls` fields.
+    macro_rules! connection {
+        ($translator:expr) => {
+            $crate::translators::translator::shared::Connection {
+                transport: &*$translator.transport,
+                timeout: $translator.request_timeout,
+                connect_timeout: $translator.connect_timeout,
+                proxy_address: $translator.proxy_address.as_deref(),
+                headers: &$translator.headers,
+                tls: $translator.tls.as_ref(),
+            }
+        };
+    }
+    pub(crate) use connection;
+
+    /// [`Chunking`](crate::translators::chunks::Chunking) of a translator from its
+    /// `text_limit`, `request_delay`, `max_concurrency` and `total_timeout` fields.
+    macro_rules! chunking {
+        ($translator:expr, $backend:expr) => {
+            $crate::translators::chunks::Chunking {
+                backend: $backend,
+                text_limit: $translator.text_limit,
+                request_delay: $translator.request_delay,
+                max_concurrency: $translator.max_concurrency,
+                total_timeout: $translator.total_timeout,
+            }
+        };
+    }
+    pub(crate) use chunking;
+}
@@ -0,0 +1,196 @@
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex};
+
+/// Public suffixes of two labels, the single labels (`com`, `fr`, ...) are
+/// rejected too.
+const PUBLIC_SUFFIXES: &[&str] = &[
+    "co.uk", "org.uk", "ac.uk", "gov.uk", "com.au", "net.au", "org.au", "co.jp", "ne.jp", "co.nz",
+    "co.in", "co.kr", "co.za", "com.br", "com.cn", "com.mx", "com.tr", "com.tw", "com.ar",
+    "com.hk", "com.sg", "co.id", "co.th",
+];
+
+/// Cookies kept between the requests of a translator.
+///
+/// Clones share the same cookies, so a jar set on a translator is used by all
+/// its clones.
+#[derive(Debug, Clone, Default)]
+pub struct CookieJar {
+    cookies: Arc<Mutex<Vec<Cookie>>>,
+}
+
+#[derive(Debug, Clone)]
+struct Cookie {
+    /// Host or parent domain, without the leading dot.
+    domain: String,
+    name: String,
+    value: String,
+}
+
+impl CookieJar {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Jar with the consent cookies of Google (`CONSENT` and `SOCS`), so the
+    /// requests from the EU don't get the consent page.
+    ///
+    /// They are sent to all the Google domains, the regional ones like
+    /// `translate.google.de` and `translate.googleapis.com` included.
+    pub fn google_consent() -> Self {
+        let jar = CookieJar::new();
+        for domain in ["google.*", "googleapis.com"] {
+            jar.set(domain, "CONSENT", "YES+cb.20210328-17-p0.en+FX+410");
+            jar.set(
+                domain,
+                "SOCS",
+                "CAISHAgBEhJnd3NfMjAyMzA4MTAtMF9SQzIaAmVuIAEaBgiAo_CmBg",
+            );
+        }
+        jar
+    }
+
+    /// Sets a cookie sent to `domain` and its subdomains.
+    ///
+    /// A domain ending with `.*` is the name under any public suffix, e.g. `google.*`
+    /// for `google.com`, `google.de` and `google.co.jp`.
+    pub fn set(&self, domain: &str, name: &str, value: &str) {
+        let domain = domain.trim_start_matches('.').to_ascii_lowercase();
+        let mut cookies = self.cookies.lock().unwrap();
+        cookies.retain(|cookie| cookie.domain != domain || cookie.name != name);
+        cookies.push(Cookie {
+            domain,
+            name: name.to_string(),
+            value: value.to_string(),
+        });
+    }
+
+    /// Value of the cookie sent to `host`.
+    pub fn get(&self, host: &str, name: &str) -> Option<String> {
+        self.cookies
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|cookie| cookie.name == name && cookie.matches(host))
+            .map(|cookie| cookie.value.clone())
+    }
+
+    pub fn clear(&self) {
+        self.cookies.lock().unwrap().clear();
+    }
+
+    /// Value of the `Cookie` header for `url`, `None` without cookies.
+    pub fn header(&self, url: &str) -> Option<String> {
+        let host = host(url);
+        let header = self
+            .cookies
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|cookie| cookie.matches(host))
+            .map(|cookie| format!("{}={}", cookie.name, cookie.value))
+            .collect::<Vec<_>>()
+            .join("; ");
+        (!header.is_empty()).then_some(header)
+    }
+
+    /// Keeps a `Set-Cookie` header received from `url`.
+    ///
+    /// The `Domain` attribute is ignored unless the host is this domain or one of
+    /// its subdomains, and isn't a public suffix.
+    pub fn store(&self, url: &str, set_cookie: &str) {
+        let mut parts = set_cookie.split(';');
+        let Some((name, value)) = parts.next().and_then(|pair| pair.split_once('=')) else {
+            return;
+        };
+        let (name, value) = (name.trim(), value.trim());
+        let host = host(url).to_ascii_lowercase();
+        let mut domain = host.clone();
+        let mut expired = false;
+        for attribute in parts {
+            let (key, attribute_value) = attribute.split_once('=').unwrap_or((attribute, ""));
+            match key.trim().to_ascii_lowercase().as_str() {
+                "domain" => {
+                    let attribute_value = attribute_value.trim().trim_start_matches('.');
+                    let attribute_value = attribute_value.to_ascii_lowercase();
+                    if allows_domain(&host, &attribute_value) {
+                        domain = attribute_value;
+                    }
+                }
+                "max-age" => {
+                    expired = attribute_value
+                        .trim()
+                        .parse::<i64>()
+                        .is_ok_and(|age| age <= 0)
+                }
+                _ => {}
+            }
+        }
+
+        if expired {
+            self.cookies
+                .lock()
+                .unwrap()
+                .retain(|cookie| cookie.domain != domain || cookie.name != name);
+        } else {
+            self.set(&domain, name, value);
+        }
+    }
+}
+
+impl Cookie {
+    fn matches(&self, host: &str) -> bool {
+        let host = host.to_ascii_lowercase();
+        match self.domain.strip_suffix(".*") {
+            Some(name) => registrable_domain(&host)
+                .and_then(|domain| domain.split_once('.'))
+                .is_some_and(|(label, _)| label == name),
+            None => is_domain_or_subdomain(&host, &self.domain),
+        }
+    }
+}
+
+fn is_domain_or_subdomain(host: &str, domain: &str) -> bool {
+    host == domain
+        || host
+            .strip_suffix(domain)
+            .is_some_and(|subdomain| subdomain.ends_with('.'))
+}
+
+/// The public suffix of `host` with the label before it, e.g. `google.co.jp` for
+/// `translate.google.co.jp`, `None` for an IP address.
+fn registrable_domain(host: &str) -> Option<&str> {
+    if host.parse::<IpAddr>().is_ok() {
+        return None;
+    }
+    let labels: Vec<_> = host.rmatch_indices('.').map(|(index, _)| index).collect();
+    let suffix_labels = match labels.get(1) {
+        Some(&index) if PUBLIC_SUFFIXES.contains(&&host[index + 1..]) => 2,
+        _ => 1,
+    };
+    match labels.get(suffix_labels) {
+        Some(&index) => Some(&host[index + 1..]),
+        None if labels.len() == suffix_labels => Some(host),
+        None => None,
+    }
+}
+
+/// Whether `host` can set a cookie for `domain`, both in lowercase.
+fn allows_domain(host: &str, domain: &str) -> bool {
+    if host == domain {
+        return true;
+    }
+    let is_subdomain = is_domain_or_subdomain(host, domain);
+    let is_public_suffix = !domain.contains('.') || PUBLIC_SUFFIXES.contains(&domain);
+    is_subdomain && !is_public_suffix && host.parse::<IpAddr>().is_err()
+}
+
+/// Host of the url, without the port, and without the brackets of an IPv6 address.
+fn host(url: &str) -> &str {
+    let url = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let authority = url.split(['/', '?', '#']).next().unwrap_or(url);
+    let authority = authority.rsplit('@').next().unwrap_or(authority);
+    match authority.strip_prefix('[') {
+        Some(address) => address.split(']').next().unwrap_or(address),
+        None => authority.split(':').next().unwrap_or(authority),
+    }
+}
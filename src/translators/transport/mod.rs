@@ -0,0 +1,244 @@
+//! HTTP transports of the translators.
+//!
+//! A [`Transport`] sends the requests of a translator, the default one depends on
+//! the enabled features: [`ReqwestTransport`] with `reqwest` (default feature),
+//! otherwise [`UreqTransport`] with `ureq`, a small blocking client for sync-only
+//! builds without tokio. Implement [`Transport`] to add middleware, reuse a
+//! configured client or test a translator without network.
+
+mod cookies;
+#[cfg(feature = "reqwest")]
+mod reqwest;
+mod tls;
+#[cfg(feature = "ureq")]
+mod ureq;
+
+#[cfg(feature = "async")]
+use crate::translators::translator::BoxFuture;
+use crate::translators::translator::Error;
+use std::fmt::Debug;
+use std::net::IpAddr;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+pub use self::cookies::CookieJar;
+#[cfg(feature = "reqwest")]
+pub use self::reqwest::ReqwestTransport;
+pub use self::tls::TlsConfig;
+#[cfg(feature = "ureq")]
+pub use self::ureq::UreqTransport;
+
+#[cfg(all(
+    any(
+        feature = "google",
+        feature = "libre",
+        feature = "deepl",
+        feature = "bing",
+        feature = "yandex",
+        feature = "mymemory",
+        feature = "lingva",
+        feature = "llm"
+    ),
+    not(any(feature = "reqwest", feature = "ureq"))
+))]
+compile_error!("the translators need a transport, enable `reqwest` or `ureq`");
+
+/// Sends the HTTP requests of a translator.
+///
+/// Any status is a valid [`Response`], errors are for requests without answer
+/// (connection, timeout, ...). The translator checks the status itself.
+///
+/// ```ignore
+/// #[derive(Debug)]
+/// struct FakeTransport;
+///
+/// impl Transport for FakeTransport {
+///     fn send_sync(&self, request: &Request) -> Result<Response, Error> {
+///         Ok(Response::new(200, &request.url, r#"<div class="result-container">Bonjour<"#))
+///     }
+///
+///     fn send_async<'a>(&'a self, request: &'a Request) -> BoxFuture<'a, Result<Response, Error>> {
+///         Box::pin(async move { self.send_sync(request) })
+///     }
+/// }
+///
+/// let translator = GoogleTranslator::builder()
+///     .transport(Arc::new(FakeTransport))
+///     .build();
+/// ```
+pub trait Transport: Debug + Send + Sync {
+    /// Sends the request, blocking the current thread.
+    fn send_sync(&self, request: &Request) -> Result<Response, Error>;
+
+    /// Sends the request.
+    #[cfg(feature = "async")]
+    fn send_async<'a>(&'a self, request: &'a Request) -> BoxFuture<'a, Result<Response, Error>>;
+}
+
+/// HTTP method of a [`Request`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    Get,
+    Post,
+}
+
+/// IP version used to connect to the servers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IpVersion {
+    V4,
+    V6,
+}
+
+impl IpVersion {
+    pub fn of(address: IpAddr) -> Self {
+        match address {
+            IpAddr::V4(_) => IpVersion::V4,
+            IpAddr::V6(_) => IpVersion::V6,
+        }
+    }
+
+    pub fn matches(self, address: IpAddr) -> bool {
+        IpVersion::of(address) == self
+    }
+}
+
+impl FromStr for IpVersion {
+    type Err = String;
+
+    /// `"4"`, `"v4"` or `"ipv4"`, same for 6.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().trim_start_matches("ip") {
+            "4" | "v4" => Ok(IpVersion::V4),
+            "6" | "v6" => Ok(IpVersion::V6),
+            _ => Err(format!("unknown ip version {s:?}")),
+        }
+    }
+}
+
+/// Request sent by a [`Transport`].
+#[derive(Debug, Clone)]
+pub struct Request {
+    pub method: Method,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<String>,
+    /// How long to wait for the whole request, from connect to the end of the body.
+    pub timeout: Duration,
+    /// How long to wait for the connection.
+    pub connect_timeout: Option<Duration>,
+    /// Proxy of the translator, see [`crate::GoogleTranslator`] for the format.
+    pub proxy_address: Option<String>,
+    /// Root certificates and client identity of the translator.
+    pub tls: Option<Arc<TlsConfig>>,
+    /// Local address the connection is bound to.
+    pub local_address: Option<IpAddr>,
+    /// Only connects to the addresses of this version.
+    pub ip_version: Option<IpVersion>,
+}
+
+impl Request {
+    /// `GET` request with the default timeout of the translators (35s).
+    pub fn get(url: impl Into<String>) -> Self {
+        Request {
+            method: Method::Get,
+            url: url.into(),
+            headers: Vec::new(),
+            body: None,
+            timeout: Duration::from_secs(35),
+            connect_timeout: None,
+            proxy_address: None,
+            tls: None,
+            local_address: None,
+            ip_version: None,
+        }
+    }
+
+    /// `POST` request with the default timeout of the translators (35s).
+    pub fn post(url: impl Into<String>, body: impl Into<String>) -> Self {
+        Request {
+            method: Method::Post,
+            body: Some(body.into()),
+            ..Request::get(url)
+        }
+    }
+
+    /// IP version of the connection: the forced one, or the one of the local address.
+    pub fn effective_ip_version(&self) -> Option<IpVersion> {
+        self.ip_version
+            .or_else(|| self.local_address.map(IpVersion::of))
+    }
+
+    /// Adds a header.
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+}
+
+/// Answer of the server, whatever the status.
+#[derive(Debug, Clone)]
+pub struct Response {
+    pub status: u16,
+    /// Url after the redirects.
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl Response {
+    /// Response without headers.
+    pub fn new(status: u16, url: impl Into<String>, body: impl Into<String>) -> Self {
+        Response {
+            status,
+            url: url.into(),
+            headers: Vec::new(),
+            body: body.into(),
+        }
+    }
+
+    /// First value of the header, the name is case-insensitive.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// All the values of the header, like `Set-Cookie`.
+    pub fn header_all<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.headers
+            .iter()
+            .filter(move |(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Path of the url, without the query.
+    pub fn path(&self) -> &str {
+        let url = self
+            .url
+            .split_once("://")
+            .map_or(&*self.url, |(_, rest)| rest);
+        let path = url.find('/').map_or("/", |start| &url[start..]);
+        path.split(['?', '#']).next().unwrap_or(path)
+    }
+}
+
+/// Transport used when none is set: reqwest, or ureq without the `reqwest` feature.
+#[cfg(any(
+    feature = "google",
+    feature = "libre",
+    feature = "deepl",
+    feature = "bing",
+    feature = "yandex",
+    feature = "mymemory",
+    feature = "lingva",
+    feature = "llm"
+))]
+pub(crate) fn default_transport() -> Arc<dyn Transport> {
+    #[cfg(feature = "reqwest")]
+    let transport = ReqwestTransport::new();
+    #[cfg(not(feature = "reqwest"))]
+    let transport = UreqTransport::new();
+    Arc::new(transport)
+}
@@ -0,0 +1,244 @@
+use super::{IpVersion, Method, Request, Response, TlsConfig, Transport};
+#[cfg(all(feature = "async", not(feature = "tokio-async")))]
+use crate::translators::runtime;
+#[cfg(feature = "async")]
+use crate::translators::translator::BoxFuture;
+use crate::translators::translator::{Error, ErrorKind};
+use reqwest::blocking::Client as ClientSync;
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use reqwest::header::HeaderMap;
+use reqwest::Client as ClientAsync;
+use reqwest::{Certificate, Identity, Proxy};
+use std::net::ToSocketAddrs;
+use std::sync::Arc;
+use std::{panic, thread};
+use tokio::runtime::Handle;
+
+/// Builds an async or a blocking client with the proxy, the connect timeout, the
+/// local address and the TLS settings of the request.
+macro_rules! build_client {
+    ($builder:expr, $request:expr) => {{
+        let (mut client, request) = ($builder, $request);
+        if let Some(connect_timeout) = request.connect_timeout {
+            client = client.connect_timeout(connect_timeout);
+        }
+        // local address and ip version
+        client = client.local_address(request.local_address);
+        if let Some(ip_version) = request.effective_ip_version() {
+            client = client.dns_resolver(Arc::new(VersionResolver(ip_version)));
+        }
+        // tls
+        if let Some(tls) = &request.tls {
+            let (certificates, identity) = tls_parts(tls)?;
+            for certificate in certificates {
+                client = client.add_root_certificate(certificate);
+            }
+            if let Some(identity) = identity {
+                client = client.identity(identity);
+            }
+            #[cfg(feature = "native-roots")]
+            {
+                client = client.tls_built_in_native_certs(tls.uses_native_roots());
+            }
+        }
+        // proxy
+        if let Some(proxy_address) = &request.proxy_address {
+            client = client.proxy(Proxy::all(proxy_address)?);
+        }
+        client.build()?
+    }};
+}
+
+/// Applies the headers, the body and the timeout of the request to an async or a
+/// blocking request builder.
+macro_rules! prepare {
+    ($builder:expr, $request:expr) => {{
+        let (mut builder, request) = ($builder, $request);
+        for (name, value) in &request.headers {
+            builder = builder.header(name, value);
+        }
+        if let Some(body) = &request.body {
+            builder = builder.body(body.clone());
+        }
+        builder.timeout(request.timeout)
+    }};
+}
+
+/// [`Transport`] based on reqwest, the default one.
+///
+/// A client is built for each request with the proxy, the connect timeout, the
+/// local address and the TLS settings of the translator, unless a preconfigured client
+/// is set: its own settings are then used, only the timeout of the request is applied.
+/// A proxy, local addresses, TLS settings or an ip version set on the translator too
+/// fail with [`ErrorKind::Builder`] instead of being ignored.
+///
+/// ```ignore
+/// let client = reqwest::Client::builder()
+///     .user_agent("my-app/1.0")
+///     .build()
+///     .unwrap();
+/// let translator = GoogleTranslator::builder()
+///     .reqwest_client(client)
+///     .build();
+/// ```
+///
+/// Without the `tokio-async` feature the async requests use the blocking client
+/// on a bounded pool of threads.
+#[derive(Debug, Clone, Default)]
+pub struct ReqwestTransport {
+    client: Option<ClientAsync>,
+    blocking_client: Option<ClientSync>,
+}
+
+impl ReqwestTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Uses this client for the async requests.
+    pub fn with_client(mut self, client: ClientAsync) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// Uses this client for the sync requests.
+    pub fn with_blocking_client(mut self, client: ClientSync) -> Self {
+        self.blocking_client = Some(client);
+        self
+    }
+
+    #[cfg(feature = "tokio-async")]
+    async fn send(&self, request: &Request) -> Result<Response, Error> {
+        let client = match &self.client {
+            Some(client) => preconfigured(client, request)?,
+            None => build_client!(ClientAsync::builder(), request),
+        };
+
+        // send req
+        let builder = match request.method {
+            Method::Get => client.get(&request.url),
+            Method::Post => client.post(&request.url),
+        };
+        let response = prepare!(builder, request).send().await?;
+
+        let (status, headers, url) = (
+            response.status().as_u16(),
+            headers(response.headers()),
+            response.url().to_string(),
+        );
+        let body = response.text().await?;
+
+        Ok(Response {
+            status,
+            url,
+            headers,
+            body,
+        })
+    }
+
+    fn send_blocking(&self, request: &Request) -> Result<Response, Error> {
+        let client = match &self.blocking_client {
+            Some(client) => preconfigured(client, request)?,
+            None => build_client!(ClientSync::builder(), request),
+        };
+
+        // send req
+        let builder = match request.method {
+            Method::Get => client.get(&request.url),
+            Method::Post => client.post(&request.url),
+        };
+        let response = prepare!(builder, request).send()?;
+
+        let (status, headers, url) = (
+            response.status().as_u16(),
+            headers(response.headers()),
+            response.url().to_string(),
+        );
+        let body = response.text()?;
+
+        Ok(Response {
+            status,
+            url,
+            headers,
+            body,
+        })
+    }
+}
+
+impl Transport for ReqwestTransport {
+    /// `reqwest::blocking` panics inside an async runtime, the request then runs on its own thread.
+    fn send_sync(&self, request: &Request) -> Result<Response, Error> {
+        if Handle::try_current().is_err() {
+            return self.send_blocking(request);
+        }
+        thread::scope(|scope| scope.spawn(|| self.send_blocking(request)).join())
+            .unwrap_or_else(|panic| panic::resume_unwind(panic))
+    }
+
+    #[cfg(feature = "tokio-async")]
+    fn send_async<'a>(&'a self, request: &'a Request) -> BoxFuture<'a, Result<Response, Error>> {
+        Box::pin(self.send(request))
+    }
+
+    #[cfg(all(feature = "async", not(feature = "tokio-async")))]
+    fn send_async<'a>(&'a self, request: &'a Request) -> BoxFuture<'a, Result<Response, Error>> {
+        let (transport, request) = (self.clone(), request.clone());
+        Box::pin(runtime::unblock(move || transport.send_blocking(&request)))
+    }
+}
+
+/// The preconfigured client, if the request has none of the settings built into a client.
+fn preconfigured<C: Clone>(client: &C, request: &Request) -> Result<C, Error> {
+    let settings = [
+        ("proxy_address", request.proxy_address.is_some()),
+        ("local_addresses", request.local_address.is_some()),
+        ("tls", request.tls.is_some()),
+        ("ip_version", request.ip_version.is_some()),
+    ];
+    match settings.iter().find(|(_, is_set)| *is_set) {
+        Some((name, _)) => Err(Error::new(
+            ErrorKind::Builder,
+            format!("`{name}` can't be applied to a preconfigured reqwest client"),
+        )),
+        None => Ok(client.clone()),
+    }
+}
+
+/// System resolver keeping only the addresses of an ip version.
+#[derive(Debug, Clone, Copy)]
+struct VersionResolver(IpVersion);
+
+impl Resolve for VersionResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let (ip_version, host) = (self.0, name.as_str().to_string());
+        Box::pin(async move {
+            let addresses =
+                tokio::task::spawn_blocking(move || (host.as_str(), 0).to_socket_addrs()).await??;
+            let addresses: Addrs = Box::new(
+                addresses
+                    .filter(move |address| ip_version.matches(address.ip()))
+                    .collect::<Vec<_>>()
+                    .into_iter(),
+            );
+            Ok(addresses)
+        })
+    }
+}
+
+/// Root certificates and client identity parsed from the PEMs.
+fn tls_parts(tls: &TlsConfig) -> Result<(Vec<Certificate>, Option<Identity>), Error> {
+    let mut certificates = Vec::new();
+    for pem in tls.root_certificates() {
+        certificates.extend(Certificate::from_pem_bundle(pem)?);
+    }
+    let identity = tls.identity().map(Identity::from_pem).transpose()?;
+
+    Ok((certificates, identity))
+}
+
+fn headers(headers: &HeaderMap) -> Vec<(String, String)> {
+    headers
+        .iter()
+        .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+        .collect()
+}
@@ -0,0 +1,56 @@
+/// TLS settings of the transports, e.g. for a TLS-inspecting proxy with a private CA.
+///
+/// The built-in roots (webpki) are always trusted, the added ones come on top.
+///
+/// ```ignore
+/// let tls = TlsConfig::new()
+///     .add_root_certificate_pem(std::fs::read("corporate-ca.pem").unwrap())
+///     .identity_pem(std::fs::read("client.pem").unwrap());
+/// let translator = GoogleTranslator::builder().tls(tls).build();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TlsConfig {
+    root_certificates: Vec<Vec<u8>>,
+    native_roots: bool,
+    identity: Option<Vec<u8>>,
+}
+
+impl TlsConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Trusts the certificates of this PEM, it can contain several certificates.
+    pub fn add_root_certificate_pem(mut self, pem: impl Into<Vec<u8>>) -> Self {
+        self.root_certificates.push(pem.into());
+        self
+    }
+
+    /// Also trusts the certificates of the OS store.
+    #[cfg(feature = "native-roots")]
+    pub fn native_roots(mut self, enabled: bool) -> Self {
+        self.native_roots = enabled;
+        self
+    }
+
+    /// Client certificate for mTLS: a PEM with the certificate chain and the
+    /// private key (PKCS#8, PKCS#1 or SEC1).
+    pub fn identity_pem(mut self, pem: impl Into<Vec<u8>>) -> Self {
+        self.identity = Some(pem.into());
+        self
+    }
+
+    /// PEMs of the added root certificates.
+    pub fn root_certificates(&self) -> &[Vec<u8>] {
+        &self.root_certificates
+    }
+
+    pub fn uses_native_roots(&self) -> bool {
+        self.native_roots
+    }
+
+    /// PEM of the client certificate and its private key.
+    pub fn identity(&self) -> Option<&[u8]> {
+        self.identity.as_deref()
+    }
+}
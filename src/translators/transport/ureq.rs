@@ -0,0 +1,173 @@
+use super::{Method, Request, Response, TlsConfig, Transport};
+#[cfg(feature = "async")]
+use crate::translators::runtime;
+#[cfg(feature = "async")]
+use crate::translators::translator::BoxFuture;
+use crate::translators::translator::{Error, ErrorKind};
+use rustls::{ClientConfig, RootCertStore};
+use std::io;
+use std::net::ToSocketAddrs;
+use std::sync::Arc;
+use ureq::{Agent, AgentBuilder, Proxy};
+
+/// Small blocking [`Transport`] based on ureq, for sync-only builds without tokio.
+///
+/// An agent is built for each request with the proxy, the connect timeout, the
+/// IP version and the TLS settings of the translator, unless a preconfigured agent
+/// is set. Binding a local address isn't supported. The async requests run
+/// on a bounded pool of threads.
+#[derive(Debug, Clone, Default)]
+pub struct UreqTransport {
+    agent: Option<Agent>,
+}
+
+impl UreqTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Uses this agent for the requests.
+    pub fn with_agent(mut self, agent: Agent) -> Self {
+        self.agent = Some(agent);
+        self
+    }
+}
+
+impl Transport for UreqTransport {
+    fn send_sync(&self, request: &Request) -> Result<Response, Error> {
+        let agent = match &self.agent {
+            Some(agent) => agent.clone(),
+            None => {
+                // agent build
+                let mut agent = AgentBuilder::new();
+                if let Some(connect_timeout) = request.connect_timeout {
+                    agent = agent.timeout_connect(connect_timeout);
+                }
+                // ip version, ureq can't bind a local address
+                if request.local_address.is_some() {
+                    return Err(Error::new(
+                        ErrorKind::Builder,
+                        "the ureq transport can't bind a local address",
+                    ));
+                }
+                if let Some(ip_version) = request.effective_ip_version() {
+                    agent = agent.resolver(move |address: &str| {
+                        let addresses = address.to_socket_addrs()?;
+                        Ok(addresses
+                            .filter(|address| ip_version.matches(address.ip()))
+                            .collect())
+                    });
+                }
+                // tls
+                if let Some(tls) = &request.tls {
+                    agent = agent.tls_config(client_config(tls)?);
+                }
+                // proxy
+                if let Some(proxy_address) = &request.proxy_address {
+                    agent = agent.proxy(Proxy::new(proxy_address)?);
+                }
+                agent.build()
+            }
+        };
+
+        // send req, an error status still has a response to check
+        let method = match request.method {
+            Method::Get => "GET",
+            Method::Post => "POST",
+        };
+        let mut builder = agent.request(method, &request.url).timeout(request.timeout);
+        for (name, value) in &request.headers {
+            builder = builder.set(name, value);
+        }
+        let result = match &request.body {
+            Some(body) => builder.send_string(body),
+            None => builder.call(),
+        };
+        let response = match result {
+            Ok(response) | Err(ureq::Error::Status(_, response)) => response,
+            Err(e) => return Err(e.into()),
+        };
+
+        let (status, url) = (response.status(), response.get_url().to_string());
+        let mut names = response.headers_names();
+        names.sort();
+        names.dedup();
+        let headers = names
+            .into_iter()
+            .flat_map(|name| {
+                let values = response.all(&name);
+                values
+                    .into_iter()
+                    .map(|value| (name.clone(), value.to_string()))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        let body = response.into_string().map_err(body_error)?;
+
+        Ok(Response {
+            status,
+            url,
+            headers,
+            body,
+        })
+    }
+
+    #[cfg(feature = "async")]
+    fn send_async<'a>(&'a self, request: &'a Request) -> BoxFuture<'a, Result<Response, Error>> {
+        let (transport, request) = (self.clone(), request.clone());
+        Box::pin(runtime::unblock(move || transport.send_sync(&request)))
+    }
+}
+
+/// rustls config with the built-in roots and the ones of `tls`.
+fn client_config(tls: &TlsConfig) -> Result<Arc<ClientConfig>, Error> {
+    let mut roots = RootCertStore {
+        roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+    };
+    #[cfg(feature = "native-roots")]
+    if tls.uses_native_roots() {
+        roots.add_parsable_certificates(rustls_native_certs::load_native_certs().certs);
+    }
+    for pem in tls.root_certificates() {
+        for certificate in rustls_pemfile::certs(&mut pem.as_slice()) {
+            roots
+                .add(certificate.map_err(tls_error)?)
+                .map_err(tls_error)?;
+        }
+    }
+
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    let builder = ClientConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()
+        .map_err(tls_error)?
+        .with_root_certificates(roots);
+    let config = match tls.identity() {
+        Some(pem) => {
+            let certificates = rustls_pemfile::certs(&mut &pem[..])
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(tls_error)?;
+            let key = rustls_pemfile::private_key(&mut &pem[..])
+                .map_err(tls_error)?
+                .ok_or_else(|| Error::new(ErrorKind::Builder, "no private key in the identity"))?;
+            builder
+                .with_client_auth_cert(certificates, key)
+                .map_err(tls_error)?
+        }
+        None => builder.with_no_client_auth(),
+    };
+
+    Ok(Arc::new(config))
+}
+
+fn tls_error(e: impl std::error::Error + Send + Sync + 'static) -> Error {
+    Error::new(ErrorKind::Builder, e.to_string()).with_source(e)
+}
+
+fn body_error(e: io::Error) -> Error {
+    let kind = match e.kind() {
+        io::ErrorKind::TimedOut | io::ErrorKind::WouldBlock => ErrorKind::Timeout,
+        io::ErrorKind::InvalidData => ErrorKind::Encoding,
+        _ => ErrorKind::DecodeBody,
+    };
+    Error::new(kind, e.to_string()).with_source(e)
+}
@@ -0,0 +1,114 @@
+use crate::translators::translator::shared::{self, Connection};
+use crate::translators::translator::{self, ErrorKind};
+use crate::translators::transport::{Request, Response};
+use serde_json::Value;
+use urlencoding::encode;
+
+/// Settings of a single request.
+#[derive(Debug, Clone, Copy)]
+pub struct RequestConfig<'a> {
+    pub connection: Connection<'a>,
+    /// Url of the translation API, without the endpoint.
+    pub url: &'a str,
+    /// Client id sent with each request.
+    pub ucid: &'a str,
+}
+
+impl RequestConfig<'_> {
+    /// Form posted to the API of the Android app.
+    fn post(&self, target_language: &str, source_language: &str, text: &str) -> Request {
+        let url = format!(
+            "{}/api/v1/tr.json/translate?ucid={}&srv=android&format=text",
+            self.url.trim_end_matches('/'),
+            encode(self.ucid)
+        );
+        let body = format!(
+            "text={}&lang={}",
+            encode(text),
+            encode(&lang_pair(target_language, source_language))
+        );
+        self.connection
+            .request(Request::post(url, body))
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .header("Accept", "application/json")
+    }
+}
+
+#[cfg(feature = "async")]
+pub async fn send_async_request(
+    target_language: &str,
+    source_language: &str,
+    text: &str,
+    config: &RequestConfig<'_>,
+) -> Result<String, translator::Error> {
+    let request = config.post(target_language, source_language, text);
+    let response = config.connection.transport.send_async(&request).await?;
+
+    get_translated_text(&check_response(response)?)
+}
+
+pub fn send_sync_request(
+    target_language: &str,
+    source_language: &str,
+    text: &str,
+    config: &RequestConfig<'_>,
+) -> Result<String, translator::Error> {
+    let request = config.post(target_language, source_language, text);
+    let response = config.connection.transport.send_sync(&request)?;
+
+    get_translated_text(&check_response(response)?)
+}
+
+/// `"en-fr"`, or only the target for the detection of the source language.
+fn lang_pair(target_language: &str, source_language: &str) -> String {
+    match source_language {
+        "" | "auto" => target_language.to_string(),
+        source_language => format!("{}-{}", source_language, target_language),
+    }
+}
+
+/// Yandex answers with `{"code": 200, ...}`, the errors have their own code and a message.
+fn check_response(response: Response) -> Result<Value, translator::Error> {
+    if response.path().contains("captcha") {
+        return Err(
+            translator::Error::new(ErrorKind::Captcha, response.url.clone())
+                .with_status(response.status),
+        );
+    }
+    let value: Option<Value> = serde_json::from_str(&response.body).ok();
+    let code = value
+        .as_ref()
+        .and_then(|value| value.get("code"))
+        .and_then(Value::as_u64)
+        .and_then(|code| u16::try_from(code).ok())
+        .unwrap_or(response.status);
+    if (200..300).contains(&response.status) && code == 200 {
+        return value.ok_or_else(|| translator::Error::new(ErrorKind::DecodeBody, response.body));
+    }
+
+    let message = value
+        .as_ref()
+        .and_then(|value| value.get("message"))
+        .and_then(Value::as_str)
+        .map_or_else(|| response.url.clone(), str::to_string);
+    let kind = match code {
+        // daily limit of the client
+        404 => ErrorKind::QuotaExceeded,
+        // text too long or that can't be translated
+        400 | 413 | 422 => ErrorKind::InvalidRequest,
+        429 => shared::rate_limited(&response),
+        // translation direction not supported
+        501 => ErrorKind::UnsupportedLanguage,
+        _ => ErrorKind::Status,
+    };
+    Err(translator::Error::new(kind, message).with_status(code))
+}
+
+fn get_translated_text(value: &Value) -> Result<String, translator::Error> {
+    // {"code": 200, "lang": "en-fr", "text": ["Bonjour"]}
+    value
+        .get("text")
+        .and_then(Value::as_array)
+        .and_then(|texts| texts.iter().map(Value::as_str).collect::<Option<String>>())
+        .ok_or_else(|| translator::Error::new(ErrorKind::DecodeBody, value.to_string()))
+}
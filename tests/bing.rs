@@ -0,0 +1,402 @@
+//! Bing against a fake web translator and a fake Azure API.
+
+mod common;
+
+use common::FakeTransport;
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+use translators::{
+    BackendConfig, BingSession, BingTranslator, ErrorKind, Method, Request, Response, Translator,
+};
+
+const PAGE: &str = r#"<html><script>_G={IG:"8D4A1B",ST:1};
+var params_AbusePreventionHelper = [1700000000000,"fake-token",3600000];</script>
+<div id="rich_tta" data-iid="translator.5028"></div></html>"#;
+
+/// Answers like Bing, the translations prefix the texts with the target.
+#[derive(Debug, Default)]
+struct Bing {
+    /// How many web requests are answered with a rejected token.
+    rejected_tokens: Mutex<usize>,
+    /// The web translator asks for a captcha.
+    captcha: bool,
+}
+
+impl Bing {
+    fn rejecting(rejected_tokens: usize) -> Self {
+        Bing {
+            rejected_tokens: Mutex::new(rejected_tokens),
+            ..Bing::default()
+        }
+    }
+
+    fn serve(self) -> Arc<FakeTransport> {
+        FakeTransport::new(move |request| self.answer(request))
+    }
+
+    fn web(&self, request: &Request) -> Value {
+        let form = form(request);
+        if *self.rejected_tokens.lock().unwrap() > 0 {
+            *self.rejected_tokens.lock().unwrap() -= 1;
+            return json!({"statusCode": 205});
+        }
+        if self.captcha {
+            return json!({"ShowCaptcha": true});
+        }
+        assert_eq!(field(&form, "token"), "fake-token");
+        assert_eq!(field(&form, "key"), "1700000000000");
+        if request.url.contains("/ttransliteratev3") {
+            return json!(format!(
+                "[{}]{}",
+                field(&form, "toScript"),
+                field(&form, "text")
+            ));
+        }
+        json!([{
+            "detectedLanguage": {"language": "en", "score": 1.0},
+            "translations": [{
+                "text": format!("[{}]{}", field(&form, "to"), field(&form, "text")),
+                "to": field(&form, "to"),
+            }],
+        }])
+    }
+
+    fn api(&self, request: &Request) -> Value {
+        let query = request.url.split_once('?').unwrap().1;
+        let param = |name: &str| {
+            query
+                .split('&')
+                .find_map(|pair| pair.strip_prefix(&format!("{}=", name)))
+                .unwrap()
+                .to_string()
+        };
+        let texts: Vec<Value> = serde_json::from_str(request.body.as_deref().unwrap()).unwrap();
+        let results = texts.iter().map(|text| {
+            let text = text["Text"].as_str().unwrap();
+            if request.url.contains("/transliterate?") {
+                json!({"text": format!("[{}]{}", param("toScript"), text), "script": "Latn"})
+            } else {
+                json!({"translations": [{"text": format!("[{}]{}", param("to"), text)}]})
+            }
+        });
+        Value::from_iter(results)
+    }
+
+    fn answer(&self, request: &Request) -> Response {
+        let path = request.url.split_once("://").unwrap().1;
+        let path = &path[path.find('/').unwrap()..];
+        let answer = if path == "/translator" {
+            return Response::new(200, &request.url, PAGE);
+        } else if path.starts_with("/ttranslatev3") || path.starts_with("/ttransliteratev3") {
+            self.web(request)
+        } else if path.starts_with("/translate?") || path.starts_with("/transliterate?") {
+            self.api(request)
+        } else {
+            return Response::new(404, &request.url, "");
+        };
+        Response::new(200, &request.url, answer.to_string())
+    }
+}
+
+fn form(request: &Request) -> Vec<(String, String)> {
+    request
+        .body
+        .as_deref()
+        .unwrap()
+        .split('&')
+        .map(|pair| {
+            let (name, value) = pair.split_once('=').unwrap();
+            (
+                name.to_string(),
+                urlencoding::decode(value).unwrap().into_owned(),
+            )
+        })
+        .collect()
+}
+
+fn field<'a>(form: &'a [(String, String)], name: &str) -> &'a str {
+    form.iter()
+        .find(|(key, _)| key == name)
+        .map(|(_, value)| value.as_str())
+        .unwrap()
+}
+
+fn web_translator(transport: Arc<FakeTransport>) -> BingTranslator {
+    BingTranslator::builder()
+        .url("http://bing.local")
+        .transport(transport)
+        .build()
+}
+
+fn azure_translator(transport: Arc<FakeTransport>) -> BingTranslator {
+    BingTranslator::builder()
+        .api_key("secret")
+        .region("westeurope")
+        .url("http://azure.local/")
+        .transport(transport)
+        .build()
+}
+
+#[test]
+fn test_api_url() {
+    assert_eq!(BingTranslator::default().api_url(), "https://www.bing.com");
+    let azure = BingTranslator::builder().api_key("secret").build();
+    assert_eq!(
+        azure.api_url(),
+        "https://api.cognitive.microsofttranslator.com"
+    );
+}
+
+#[test]
+fn test_web_translate() {
+    let transport = Bing::default().serve();
+    let translator = web_translator(transport.clone());
+
+    let res = translator.translate_sync("Hello world", "", "fr").unwrap();
+    assert_eq!(res, "[fr]Hello world");
+    let res = translator.translate_sync("Goodbye", "en", "de").unwrap();
+    assert_eq!(res, "[de]Goodbye");
+    assert!(translator.session.has_token());
+
+    // the page is fetched once for both translations
+    assert_eq!(
+        transport.urls(),
+        [
+            "http://bing.local/translator",
+            "http://bing.local/ttranslatev3?isVertical=1&IG=8D4A1B&IID=translator.5028",
+            "http://bing.local/ttranslatev3?isVertical=1&IG=8D4A1B&IID=translator.5028",
+        ]
+    );
+    let requests = transport.requests();
+    assert_eq!(requests[1].method, Method::Post);
+    assert_eq!(field(&form(&requests[1]), "fromLang"), "auto-detect");
+    assert_eq!(field(&form(&requests[2]), "fromLang"), "en");
+    assert!(requests[1].headers.contains(&(
+        "Content-Type".to_string(),
+        "application/x-www-form-urlencoded".to_string()
+    )));
+}
+
+#[test]
+fn test_web_session_shared() {
+    let transport = Bing::default().serve();
+    let translator = web_translator(transport.clone());
+    let clone = translator.clone();
+
+    translator.translate_sync("Hello", "", "fr").unwrap();
+    clone.translate_sync("Hello", "", "fr").unwrap();
+    assert_eq!(transport.urls().len(), 3);
+
+    translator.session.clear();
+    assert!(!clone.session.has_token());
+    clone.translate_sync("Hello", "", "fr").unwrap();
+    assert_eq!(transport.urls().len(), 5);
+
+    // a new session fetches its own page
+    let other = BingTranslator {
+        session: BingSession::new(),
+        ..translator
+    };
+    other.translate_sync("Hello", "", "fr").unwrap();
+    assert_eq!(transport.urls().len(), 7);
+}
+
+#[test]
+fn test_web_rejected_token() {
+    // the page is fetched again once
+    let transport = Bing::rejecting(1).serve();
+    let translator = web_translator(transport.clone());
+    let res = translator.translate_sync("Hello", "", "fr").unwrap();
+    assert_eq!(res, "[fr]Hello");
+    assert_eq!(transport.urls().len(), 4);
+
+    let transport = Bing::rejecting(2).serve();
+    let err = web_translator(transport.clone())
+        .translate_sync("Hello", "", "fr")
+        .unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::InvalidRequest);
+    assert_eq!(err.backend(), Some("bing"));
+    assert_eq!(transport.urls().len(), 4);
+}
+
+#[test]
+fn test_web_captcha() {
+    let transport = Bing {
+        captcha: true,
+        ..Bing::default()
+    }
+    .serve();
+    let err = web_translator(transport)
+        .translate_sync("Hello", "", "fr")
+        .unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::Captcha);
+    assert_eq!(err.backend(), Some("bing"));
+}
+
+#[test]
+fn test_web_batch_and_transliterate() {
+    let transport = Bing::default().serve();
+    let translator = web_translator(transport.clone());
+
+    let res = translator
+        .translate_batch_sync(&["One", "Two"], "en", "fr")
+        .unwrap();
+    assert_eq!(res, ["[fr]One", "[fr]Two"]);
+    let res = translator
+        .transliterate_sync("こんにちは", "ja", "Jpan", "Latn")
+        .unwrap();
+    assert_eq!(res, "[Latn]こんにちは");
+    let res = translator
+        .transliterate_batch_sync(&["こんにちは", "さようなら"], "ja", "Jpan", "Latn")
+        .unwrap();
+    assert_eq!(res, ["[Latn]こんにちは", "[Latn]さようなら"]);
+    // one page and a request per text
+    assert_eq!(transport.urls().len(), 6);
+}
+
+#[test]
+fn test_azure_translate() {
+    let transport = Bing::default().serve();
+    let translator = azure_translator(transport.clone());
+
+    let res = translator.translate_sync("Hello", "", "fr").unwrap();
+    assert_eq!(res, "[fr]Hello");
+    let res = translator.translate_sync("Hello", "en", "de").unwrap();
+    assert_eq!(res, "[de]Hello");
+
+    let requests = transport.requests();
+    assert_eq!(
+        requests[0].url,
+        "http://azure.local/translate?api-version=3.0&to=fr"
+    );
+    assert_eq!(
+        requests[1].url,
+        "http://azure.local/translate?api-version=3.0&to=de&from=en"
+    );
+    assert!(requests[0].headers.contains(&(
+        "Ocp-Apim-Subscription-Key".to_string(),
+        "secret".to_string()
+    )));
+    assert!(requests[0].headers.contains(&(
+        "Ocp-Apim-Subscription-Region".to_string(),
+        "westeurope".to_string()
+    )));
+    assert_eq!(requests[0].body.as_deref(), Some(r#"[{"Text":"Hello"}]"#));
+}
+
+#[test]
+fn test_azure_batch() {
+    let transport = Bing::default().serve();
+    let translator = BingTranslator {
+        text_limit: 10,
+        ..azure_translator(transport.clone())
+    };
+
+    let res = translator
+        .translate_batch_sync(&["One", "Two", "Three", "Long long text"], "en", "fr")
+        .unwrap();
+    assert_eq!(
+        res,
+        ["[fr]One", "[fr]Two", "[fr]Three", "[fr]Long long [fr]text"]
+    );
+    // "One" and "Two", then "Three", then the long text in 2 chunks
+    assert_eq!(transport.urls().len(), 4);
+
+    let transport = Bing::default().serve();
+    let translator = azure_translator(transport.clone());
+    let texts = vec!["こんにちは"; 12];
+    let res = translator
+        .transliterate_batch_sync(&texts, "ja", "Jpan", "Latn")
+        .unwrap();
+    assert_eq!(res, vec!["[Latn]こんにちは"; 12]);
+    // up to 10 texts a request
+    assert_eq!(transport.urls().len(), 2);
+    assert_eq!(
+        transport.urls()[0],
+        "http://azure.local/transliterate?api-version=3.0&language=ja&fromScript=Jpan&toScript=Latn"
+    );
+}
+
+#[test]
+fn test_azure_errors() {
+    let transport = FakeTransport::failing(
+        403,
+        r#"{"error": {"code": 403001, "message": "The operation is not allowed because the subscription has exceeded its free quota."}}"#,
+    );
+    let err = azure_translator(transport)
+        .translate_sync("Hello", "en", "fr")
+        .unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::QuotaExceeded);
+    assert_eq!(err.status(), Some(403));
+    assert_eq!(err.backend(), Some("bing"));
+    assert!(!err.is_retryable());
+
+    let transport = FakeTransport::failing(
+        400,
+        r#"{"error": {"code": 400036, "message": "The target language is not valid."}}"#,
+    );
+    let err = azure_translator(transport)
+        .translate_batch_sync(&["One", "Two"], "en", "xx")
+        .unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::InvalidRequest);
+    assert_eq!(err.message(), "The target language is not valid.");
+
+    let transport = FakeTransport::failing(429, "");
+    let err = azure_translator(transport)
+        .transliterate_sync("こんにちは", "ja", "Jpan", "Latn")
+        .unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::RateLimited { .. }));
+
+    let err = BingTranslator::default()
+        .translate_sync("Hello", "en", "auto")
+        .unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::UnsupportedLanguage);
+}
+
+#[test]
+fn test_from_config() {
+    let config = BackendConfig::new()
+        .set("api_key", "secret")
+        .set("region", "westeurope")
+        .set("text_limit", "50000");
+    let translator = BingTranslator::from_config(&config).unwrap();
+    assert_eq!(translator.api_key.as_deref(), Some("secret"));
+    assert_eq!(translator.region.as_deref(), Some("westeurope"));
+    assert_eq!(translator.text_limit, 50000);
+
+    let translator = BingTranslator::from_config(&BackendConfig::new()).unwrap();
+    assert_eq!(translator.api_key, None);
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn test_translate_async() {
+    let transport = Bing::default().serve();
+    let translator = web_translator(transport.clone());
+    let res = futures::executor::block_on(translator.translate_async("Hello", "", "fr")).unwrap();
+    assert_eq!(res, "[fr]Hello");
+    let res = futures::executor::block_on(translator.transliterate_async(
+        "こんにちは",
+        "ja",
+        "Jpan",
+        "Latn",
+    ))
+    .unwrap();
+    assert_eq!(res, "[Latn]こんにちは");
+
+    let transport = Bing::default().serve();
+    let translator = azure_translator(transport.clone());
+    let res =
+        futures::executor::block_on(translator.translate_batch_async(&["One", "Two"], "en", "fr"))
+            .unwrap();
+    assert_eq!(res, ["[fr]One", "[fr]Two"]);
+    let res = futures::executor::block_on(translator.transliterate_batch_async(
+        &["こんにちは"],
+        "ja",
+        "Jpan",
+        "Latn",
+    ))
+    .unwrap();
+    assert_eq!(res, ["[Latn]こんにちは"]);
+    assert_eq!(transport.urls().len(), 2);
+}
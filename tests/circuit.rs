@@ -0,0 +1,111 @@
+use std::thread;
+use std::time::Duration;
+
+use translators::{CircuitBreaker, CircuitState, Error, ErrorKind};
+
+#[test]
+fn test_circuit_opens_at_threshold() {
+    let breaker = CircuitBreaker::new(3, Duration::from_secs(60));
+    assert_eq!(breaker.state(), CircuitState::Closed);
+
+    breaker.record_failure();
+    breaker.record_failure();
+    assert_eq!(breaker.state(), CircuitState::Closed);
+    assert!(!breaker.try_acquire().unwrap().is_probe());
+    // a success resets the count
+    breaker.record_success();
+    assert_eq!(breaker.consecutive_failures(), 0);
+
+    for _ in 0..3 {
+        breaker.record_failure();
+    }
+    assert_eq!(breaker.state(), CircuitState::Open);
+    assert!(breaker.try_acquire().is_none());
+    let retry_after = breaker.retry_after().unwrap();
+    assert!(retry_after > Duration::from_secs(59));
+
+    breaker.reset();
+    assert_eq!(breaker.state(), CircuitState::Closed);
+    assert_eq!(breaker.retry_after(), None);
+}
+
+#[test]
+fn test_circuit_half_open_probe() {
+    let breaker = CircuitBreaker::new(1, Duration::from_millis(20));
+    breaker.record_failure();
+    assert_eq!(breaker.state(), CircuitState::Open);
+    thread::sleep(Duration::from_millis(30));
+    assert_eq!(breaker.state(), CircuitState::HalfOpen);
+
+    // a single probe at a time
+    let probe = breaker.try_acquire().unwrap();
+    assert!(probe.is_probe());
+    assert!(breaker.try_acquire().is_none());
+    // the probe failed, open again
+    probe.record_failure();
+    drop(probe);
+    assert_eq!(breaker.state(), CircuitState::Open);
+
+    thread::sleep(Duration::from_millis(30));
+    breaker.try_acquire().unwrap().record_success();
+    assert_eq!(breaker.state(), CircuitState::Closed);
+    assert!(breaker.try_acquire().is_some());
+}
+
+#[test]
+fn test_circuit_dropped_probe() {
+    let breaker = CircuitBreaker::new(1, Duration::from_millis(20));
+    breaker.record_failure();
+    thread::sleep(Duration::from_millis(30));
+
+    // a cancelled probe doesn't block the circuit
+    let probe = breaker.try_acquire().unwrap();
+    assert!(breaker.try_acquire().is_none());
+    drop(probe);
+    assert_eq!(breaker.state(), CircuitState::HalfOpen);
+    let probe = breaker.try_acquire().unwrap();
+    assert!(probe.is_probe());
+    probe.record_success();
+    assert_eq!(breaker.state(), CircuitState::Closed);
+}
+
+#[test]
+fn test_circuit_dropped_probe_future() {
+    use futures::FutureExt;
+
+    let breaker = CircuitBreaker::new(1, Duration::from_millis(20));
+    breaker.record_failure();
+    thread::sleep(Duration::from_millis(30));
+
+    let request = async {
+        let _permit = breaker.try_acquire().unwrap();
+        futures::future::pending::<()>().await;
+    };
+    let mut request = Box::pin(request);
+    assert!((&mut request).now_or_never().is_none());
+    assert!(breaker.try_acquire().is_none());
+    drop(request);
+    assert!(breaker.try_acquire().unwrap().is_probe());
+}
+
+#[test]
+fn test_circuit_shared_by_clones() {
+    let breaker = CircuitBreaker::new(2, Duration::from_secs(60));
+    let clone = breaker.clone();
+    breaker.record_failure();
+    clone.record_failure();
+    assert_eq!(breaker.state(), CircuitState::Open);
+    assert_eq!(clone.consecutive_failures(), 2);
+}
+
+#[test]
+fn test_circuit_open_error() {
+    let retry_after = Some(Duration::from_secs(30));
+    let err = Error::new(ErrorKind::CircuitOpen { retry_after }, "too many captchas")
+        .with_backend("google");
+    assert!(err.is_retryable());
+    assert_eq!(
+        err.to_string(),
+        "google: Circuit open, retry after 30s: too many captchas"
+    );
+}
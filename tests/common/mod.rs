@@ -0,0 +1,88 @@
+//! Fake transport shared by the tests of the translators.
+#![allow(dead_code)]
+
+use serde_json::Value;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+#[cfg(feature = "async")]
+use translators::BoxFuture;
+use translators::{Error, Request, Response, Transport};
+
+type Answer = Box<dyn Fn(&Request) -> Response + Send + Sync>;
+
+/// Answers like a fake service and keeps the requests.
+pub struct FakeTransport {
+    answer: Answer,
+    /// Status and body of every answer instead of the normal ones.
+    error: Option<(u16, String)>,
+    requests: Mutex<Vec<Request>>,
+}
+
+impl FakeTransport {
+    /// Answers the requests with `answer`.
+    pub fn new(answer: impl Fn(&Request) -> Response + Send + Sync + 'static) -> Arc<Self> {
+        Arc::new(FakeTransport {
+            answer: Box::new(answer),
+            error: None,
+            requests: Mutex::new(Vec::new()),
+        })
+    }
+
+    /// Answers every request with the same response.
+    pub fn responding(response: Response) -> Arc<Self> {
+        FakeTransport::new(move |_| response.clone())
+    }
+
+    /// Answers every request with this status and body.
+    pub fn failing(status: u16, body: &str) -> Arc<Self> {
+        Arc::new(FakeTransport {
+            answer: Box::new(|_| unreachable!("the error replaces the answers")),
+            error: Some((status, body.to_string())),
+            requests: Mutex::new(Vec::new()),
+        })
+    }
+
+    pub fn requests(&self) -> Vec<Request> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    pub fn urls(&self) -> Vec<String> {
+        let requests = self.requests.lock().unwrap();
+        requests.iter().map(|request| request.url.clone()).collect()
+    }
+
+    /// JSON bodies of the requests, the ones without body are left out.
+    pub fn bodies(&self) -> Vec<Value> {
+        let requests = self.requests.lock().unwrap();
+        requests
+            .iter()
+            .filter_map(|request| request.body.as_deref())
+            .map(|body| serde_json::from_str(body).unwrap())
+            .collect()
+    }
+}
+
+impl Transport for FakeTransport {
+    fn send_sync(&self, request: &Request) -> Result<Response, Error> {
+        self.requests.lock().unwrap().push(request.clone());
+        if let Some((status, body)) = &self.error {
+            return Ok(Response::new(*status, &request.url, body.clone()));
+        }
+        Ok((self.answer)(request))
+    }
+
+    #[cfg(feature = "async")]
+    fn send_async<'a>(&'a self, request: &'a Request) -> BoxFuture<'a, Result<Response, Error>> {
+        Box::pin(async move { self.send_sync(request) })
+    }
+}
+
+impl fmt::Debug for FakeTransport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FakeTransport")
+            .field("error", &self.error)
+            .field("requests", &self.requests.lock().unwrap().len())
+            .finish()
+    }
+}
@@ -0,0 +1,152 @@
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use translators::AdaptiveConcurrency;
+
+#[test]
+fn test_additive_increase() {
+    let concurrency = AdaptiveConcurrency::new(2, 4);
+    assert_eq!(concurrency.limit(), 2);
+
+    // +1/limit per success, about one more request per round
+    for _ in 0..3 {
+        concurrency.try_acquire().unwrap().record_success();
+    }
+    assert_eq!(concurrency.limit(), 3);
+    for _ in 0..20 {
+        concurrency.try_acquire().unwrap().record_success();
+    }
+    assert_eq!(concurrency.limit(), 4);
+    assert_eq!(concurrency.in_flight(), 0);
+}
+
+#[test]
+fn test_multiplicative_decrease() {
+    let concurrency = AdaptiveConcurrency::new(2, 16);
+    for _ in 0..200 {
+        concurrency.try_acquire().unwrap().record_success();
+    }
+    assert_eq!(concurrency.limit(), 16);
+
+    let permits: Vec<_> = (0..3).map(|_| concurrency.try_acquire().unwrap()).collect();
+    permits[0].record_overload();
+    assert_eq!(concurrency.limit(), 8);
+    // sent before the decrease, same overload
+    permits[1].record_overload();
+    assert_eq!(concurrency.limit(), 8);
+
+    let permit = concurrency.try_acquire();
+    assert!(permit.is_some());
+    for _ in 0..5 {
+        permit.as_ref().unwrap().record_overload();
+    }
+    assert_eq!(concurrency.limit(), 4);
+    drop(permit);
+    drop(permits);
+
+    for _ in 0..3 {
+        concurrency.try_acquire().unwrap().record_overload();
+    }
+    // never below the minimum
+    assert_eq!(concurrency.limit(), 2);
+}
+
+#[test]
+fn test_latency_threshold() {
+    let concurrency = AdaptiveConcurrency::new(1, 4).with_latency_threshold(Duration::ZERO);
+    for _ in 0..4 {
+        let permit = concurrency.try_acquire().unwrap();
+        thread::sleep(Duration::from_millis(1));
+        permit.record_success();
+    }
+    assert_eq!(concurrency.limit(), 1);
+}
+
+#[test]
+fn test_limit() {
+    let concurrency = AdaptiveConcurrency::new(2, 2);
+    let first = concurrency.try_acquire().unwrap();
+    let _second = concurrency.try_acquire().unwrap();
+    assert!(concurrency.try_acquire().is_none());
+    assert_eq!(concurrency.in_flight(), 2);
+
+    // a waiting thread gets the permit of the first request
+    let (sender, receiver) = mpsc::channel();
+    let clone = concurrency.clone();
+    let waiter = thread::spawn(move || {
+        let _permit = clone.acquire_sync();
+        sender.send(()).unwrap();
+    });
+    thread::sleep(Duration::from_millis(20));
+    assert!(receiver.try_recv().is_err());
+    drop(first);
+    waiter.join().unwrap();
+    assert!(receiver.try_recv().is_ok());
+    assert_eq!(concurrency.in_flight(), 1);
+}
+
+#[test]
+fn test_bounds() {
+    let concurrency = AdaptiveConcurrency::new(0, 0);
+    assert_eq!(concurrency.min(), 1);
+    assert_eq!(concurrency.max(), 1);
+    assert_eq!(concurrency.limit(), 1);
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn test_acquire_async() {
+    use futures::FutureExt;
+
+    let concurrency = AdaptiveConcurrency::new(1, 1);
+    let permit = futures::executor::block_on(concurrency.acquire_async());
+
+    let mut waiting = Box::pin(concurrency.acquire_async());
+    assert!((&mut waiting).now_or_never().is_none());
+    let mut cancelled = Box::pin(concurrency.acquire_async());
+    assert!((&mut cancelled).now_or_never().is_none());
+    drop(cancelled);
+
+    drop(permit);
+    let permit = futures::executor::block_on(waiting);
+    assert_eq!(concurrency.in_flight(), 1);
+    // the cancelled waiter doesn't keep the permit
+    drop(permit);
+    assert_eq!(concurrency.in_flight(), 0);
+    assert!(concurrency.try_acquire().is_some());
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn test_cancelled_while_woken() {
+    use futures::FutureExt;
+    use std::sync::Barrier;
+
+    // the waiter gives up while the released permit is handed to it
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let concurrency = AdaptiveConcurrency::new(1, 1);
+        for _ in 0..5000 {
+            let permit = concurrency.try_acquire().unwrap();
+            let mut waiting = Box::pin(concurrency.acquire_async());
+            assert!((&mut waiting).now_or_never().is_none());
+            let barrier = Barrier::new(2);
+            thread::scope(|scope| {
+                let barrier = &barrier;
+                scope.spawn(move || {
+                    barrier.wait();
+                    drop(waiting);
+                });
+                barrier.wait();
+                drop(permit);
+            });
+            assert_eq!(concurrency.in_flight(), 0);
+        }
+        sender.send(()).unwrap();
+    });
+
+    receiver
+        .recv_timeout(Duration::from_secs(60))
+        .expect("the permits are released without a deadlock");
+}
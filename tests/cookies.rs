@@ -0,0 +1,122 @@
+use translators::CookieJar;
+
+#[test]
+fn test_cookie_domains() {
+    let jar = CookieJar::new();
+    jar.set(".google.com", "NID", "1");
+    jar.set("translate.google.com", "A", "2");
+
+    assert_eq!(jar.get("translate.google.com", "NID").as_deref(), Some("1"));
+    assert_eq!(jar.get("google.com", "A"), None);
+    assert_eq!(jar.get("notgoogle.com", "NID"), None);
+    assert_eq!(
+        jar.header("https://translate.google.com:443/m?q=a")
+            .as_deref(),
+        Some("NID=1; A=2")
+    );
+    assert_eq!(jar.header("https://example.com/"), None);
+}
+
+#[test]
+fn test_cookie_store() {
+    let jar = CookieJar::new();
+    jar.store(
+        "https://translate.google.com/m",
+        "NID=511=abc; expires=Sat, 01-Jan-2030 00:00:00 GMT; path=/; domain=.google.com; HttpOnly",
+    );
+    jar.store("https://translate.google.com/m", "AEC=xyz; Path=/");
+
+    assert_eq!(jar.get("www.google.com", "NID").as_deref(), Some("511=abc"));
+    assert_eq!(
+        jar.get("translate.google.com", "AEC").as_deref(),
+        Some("xyz")
+    );
+    assert_eq!(jar.get("www.google.com", "AEC"), None);
+
+    jar.store("https://translate.google.com/m", "AEC=; Max-Age=0");
+    assert_eq!(jar.get("translate.google.com", "AEC"), None);
+}
+
+#[test]
+fn test_cookie_store_foreign_domain() {
+    let jar = CookieJar::new();
+    let url = "https://translate.google.com/m";
+    jar.store(url, "A=1; Domain=example.com");
+    jar.store(url, "B=2; Domain=.com");
+    jar.store("https://www.bbc.co.uk/", "C=3; Domain=co.uk");
+    jar.store(url, "D=4; Domain=oogle.com");
+
+    // kept for the host only
+    for (host, name) in [
+        ("translate.google.com", "A"),
+        ("translate.google.com", "B"),
+        ("www.bbc.co.uk", "C"),
+        ("translate.google.com", "D"),
+    ] {
+        assert!(jar.get(host, name).is_some(), "{name}");
+    }
+    assert_eq!(jar.get("example.com", "A"), None);
+    assert_eq!(jar.get("www.google.com", "B"), None);
+    assert_eq!(jar.get("news.co.uk", "C"), None);
+    assert_eq!(jar.get("oogle.com", "D"), None);
+
+    jar.store("https://www.bbc.co.uk/", "E=5; Domain=BBC.co.uk");
+    assert_eq!(jar.get("news.bbc.co.uk", "E").as_deref(), Some("5"));
+    jar.store("http://10.0.0.1/", "F=6; Domain=0.1");
+    assert_eq!(jar.get("0.1", "F"), None);
+}
+
+#[test]
+fn test_cookie_jar_shared() {
+    let jar = CookieJar::google_consent();
+    let clone = jar.clone();
+    clone.set("google.com", "NID", "1");
+
+    assert!(jar.get("translate.google.com", "CONSENT").is_some());
+    assert!(jar.get("translate.google.com", "SOCS").is_some());
+    assert_eq!(jar.get("translate.google.com", "NID").as_deref(), Some("1"));
+
+    jar.clear();
+    assert_eq!(clone.header("https://translate.google.com/"), None);
+}
+
+#[test]
+fn test_google_consent_domains() {
+    let jar = CookieJar::google_consent();
+    for url in [
+        "https://translate.google.com/m",
+        "https://translate.googleapis.com/translate_a/single",
+        "https://translate.google.de/m",
+        "https://translate.google.co.jp/m",
+        "https://google.com.br/",
+    ] {
+        let header = jar.header(url).unwrap();
+        assert!(
+            header.contains("CONSENT=") && header.contains("SOCS="),
+            "{url}"
+        );
+    }
+    for url in [
+        "https://notgoogle.de/",
+        "https://google.example.com/",
+        "https://co.jp/",
+        "http://127.0.0.1/",
+    ] {
+        assert_eq!(jar.header(url), None, "{url}");
+    }
+}
+
+#[test]
+fn test_cookie_ipv6_host() {
+    let jar = CookieJar::new();
+    jar.store("http://[::1]:8080/m", "A=1; Path=/");
+    jar.store("http://[::1]:8080/m", "B=2; Domain=1");
+
+    assert_eq!(jar.get("::1", "A").as_deref(), Some("1"));
+    assert_eq!(
+        jar.header("http://[::1]:8080/").as_deref(),
+        Some("A=1; B=2")
+    );
+    assert_eq!(jar.header("http://[::2]/"), None);
+    assert_eq!(jar.header("http://127.0.0.1:8080/"), None);
+}
@@ -0,0 +1,295 @@
+//! DeepL against a fake API.
+
+mod common;
+
+use common::FakeTransport;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use translators::{
+    BackendConfig, DeepLTranslator, DeepLUsage, ErrorKind, Formality, Method, Request, Response,
+    TagHandling, Translator,
+};
+
+/// Answers like the DeepL API, `/v2/translate` prefixes the texts with the target.
+fn deepl(request: &Request) -> Response {
+    let answer = match request.url.split_once("/v2/").unwrap().1 {
+        "translate" => {
+            let body: Value = serde_json::from_str(request.body.as_deref().unwrap()).unwrap();
+            let target = body["target_lang"].as_str().unwrap();
+            let translations: Vec<Value> = body["text"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|text| {
+                    json!({
+                        "detected_source_language": "EN",
+                        "text": format!("[{}]{}", target, text.as_str().unwrap()),
+                    })
+                })
+                .collect();
+            json!({ "translations": translations })
+        }
+        "usage" => json!({"character_count": 180118, "character_limit": 500000}),
+        "languages?type=target" => json!([
+            {"language": "DE", "name": "German", "supports_formality": true},
+            {"language": "EN-GB", "name": "English (British)", "supports_formality": false},
+        ]),
+        _ => return Response::new(404, &request.url, ""),
+    };
+    Response::new(200, &request.url, answer.to_string())
+}
+
+fn translator(transport: Arc<FakeTransport>) -> DeepLTranslator {
+    DeepLTranslator::builder()
+        .api_key("secret:fx")
+        .url("http://deepl.local/")
+        .transport(transport)
+        .build()
+}
+
+#[test]
+fn test_api_url() {
+    let free = DeepLTranslator::builder().api_key("secret:fx").build();
+    assert_eq!(free.api_url(), "https://api-free.deepl.com");
+    let pro = DeepLTranslator::builder().api_key("secret").build();
+    assert_eq!(pro.api_url(), "https://api.deepl.com");
+}
+
+#[test]
+fn test_translate() {
+    let transport = FakeTransport::new(deepl);
+    let translator = DeepLTranslator::builder()
+        .api_key("secret:fx")
+        .url("http://deepl.local/")
+        .formality(Formality::PreferLess)
+        .glossary_id("def3a26b-3e84-45b3-84ae-0c0aaf3525f7")
+        .tag_handling(TagHandling::Html)
+        .transport(transport.clone())
+        .build();
+
+    let res = translator.translate_sync("Hello", "en", "de").unwrap();
+    assert_eq!(res, "[DE]Hello");
+
+    let requests = transport.requests();
+    assert_eq!(requests[0].method, Method::Post);
+    assert_eq!(requests[0].url, "http://deepl.local/v2/translate");
+    assert!(requests[0].headers.contains(&(
+        "Authorization".to_string(),
+        "DeepL-Auth-Key secret:fx".to_string()
+    )));
+    drop(requests);
+    assert_eq!(
+        transport.bodies()[0],
+        json!({
+            "text": ["Hello"],
+            "source_lang": "EN",
+            "target_lang": "DE",
+            "formality": "prefer_less",
+            "glossary_id": "def3a26b-3e84-45b3-84ae-0c0aaf3525f7",
+            "tag_handling": "html",
+        })
+    );
+}
+
+#[test]
+fn test_target_variants() {
+    let transport = FakeTransport::new(deepl);
+    let translator = translator(transport.clone());
+
+    // `EN` and `PT` are deprecated as targets
+    for (target, code) in [
+        ("en", "EN-US"),
+        ("pt", "PT-PT"),
+        ("en-gb", "EN-GB"),
+        ("pt-BR", "PT-BR"),
+    ] {
+        let res = translator.translate_sync("Hallo", "de", target).unwrap();
+        assert_eq!(res, format!("[{code}]Hallo"));
+    }
+    let res = translator.translate_sync("Hello", "en", "de").unwrap();
+    assert_eq!(res, "[DE]Hello");
+    assert_eq!(transport.bodies()[4]["source_lang"], "EN");
+}
+
+#[test]
+fn test_glossary_without_source() {
+    let transport = FakeTransport::new(deepl);
+    let translator = DeepLTranslator {
+        glossary_id: Some("def3a26b-3e84-45b3-84ae-0c0aaf3525f7".to_string()),
+        ..translator(transport.clone())
+    };
+
+    for source in ["", "auto"] {
+        let err = translator
+            .translate_sync("Hello", source, "de")
+            .unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::Builder);
+        assert_eq!(err.backend(), Some("deepl"));
+        let err = translator
+            .translate_batch_sync(&["Hello"], source, "de")
+            .unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::Builder);
+    }
+    assert!(transport.requests().is_empty());
+}
+
+#[test]
+fn test_translate_auto_chunks() {
+    let transport = FakeTransport::new(deepl);
+    let translator = DeepLTranslator::builder()
+        .api_key("secret")
+        .text_limit(6usize)
+        .transport(transport.clone())
+        .build();
+
+    let res = translator.translate_sync("Hello world", "", "fr").unwrap();
+    assert_eq!(res, "[FR]Hello [FR]world");
+    let bodies = transport.bodies();
+    assert_eq!(bodies.len(), 2);
+    assert!(bodies.iter().all(|body| body.get("source_lang").is_none()));
+    let requests = transport.requests();
+    assert!(requests[0].url.starts_with("https://api.deepl.com/v2/"));
+}
+
+#[test]
+fn test_translate_batch() {
+    let transport = FakeTransport::new(deepl);
+    let translator = DeepLTranslator::builder()
+        .api_key("secret:fx")
+        .url("http://deepl.local")
+        .text_limit(10usize)
+        .transport(transport.clone())
+        .build();
+
+    let texts = ["One", "Two", "Three", "A long text"];
+    let res = translator.translate_batch_sync(&texts, "en", "de").unwrap();
+    assert_eq!(
+        res,
+        ["[DE]One", "[DE]Two", "[DE]Three", "[DE]A long [DE]text"]
+    );
+    let batches: Vec<Value> = transport
+        .bodies()
+        .into_iter()
+        .map(|body| body["text"].clone())
+        .collect();
+    assert_eq!(
+        batches,
+        [
+            json!(["One", "Two"]),
+            json!(["Three"]),
+            json!(["A long "]),
+            json!(["text"])
+        ]
+    );
+
+    let many: Vec<&str> = vec!["a"; 120];
+    let translator = DeepLTranslator {
+        text_limit: 1000,
+        ..translator
+    };
+    let res = translator.translate_batch_sync(&many, "en", "de").unwrap();
+    assert_eq!(res.len(), 120);
+    // 4 requests above and 50 + 50 + 20 texts
+    assert_eq!(transport.bodies().len(), 7);
+}
+
+#[test]
+fn test_errors() {
+    let transport = FakeTransport::failing(456, r#"{"message": "Quota exceeded"}"#);
+    let err = translator(transport)
+        .translate_sync("Hello", "en", "de")
+        .unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::QuotaExceeded);
+    assert_eq!(err.message(), "Quota exceeded");
+    assert_eq!(err.status(), Some(456));
+    assert_eq!(err.backend(), Some("deepl"));
+    assert!(!err.is_retryable());
+
+    let transport = FakeTransport::failing(403, r#"{"message": "Wrong key"}"#);
+    let err = translator(transport).usage_sync().unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::Status);
+    assert_eq!(err.status(), Some(403));
+    assert_eq!(err.backend(), Some("deepl"));
+
+    for status in [429, 529] {
+        let transport = FakeTransport::failing(status, "");
+        let err = translator(transport)
+            .translate_batch_sync(&["Hello"], "en", "de")
+            .unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::RateLimited { .. }));
+    }
+
+    // the failed group of texts
+    let transport = FakeTransport::failing(456, r#"{"message": "Quota exceeded"}"#);
+    let err = translator(transport)
+        .translate_batch_sync(&["One", "Two"], "en", "de")
+        .unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::QuotaExceeded);
+    assert_eq!(err.chunk_index(), Some(0));
+    assert_eq!(err.chunk_range(), Some(0..2));
+
+    let transport = FakeTransport::failing(
+        400,
+        r#"{"message": "Value for 'target_lang' not supported."}"#,
+    );
+    let err = translator(transport)
+        .translate_sync("Hello", "en", "xx")
+        .unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::InvalidRequest);
+    assert_eq!(err.chunk_index(), Some(0));
+}
+
+#[test]
+fn test_usage_and_languages() {
+    let transport = FakeTransport::new(deepl);
+    let translator = translator(transport.clone());
+
+    let usage = translator.usage_sync().unwrap();
+    assert_eq!(
+        usage,
+        DeepLUsage {
+            character_count: 180118,
+            character_limit: 500000
+        }
+    );
+    assert_eq!(usage.remaining(), 319882);
+
+    let languages = translator.fetch_languages_sync().unwrap();
+    assert_eq!(languages[1].code, "EN-GB");
+    let requests = transport.requests();
+    assert_eq!(requests[0].method, Method::Get);
+    assert_eq!(requests[0].url, "http://deepl.local/v2/usage");
+}
+
+#[test]
+fn test_from_config() {
+    let config = BackendConfig::new()
+        .set("api_key", "secret")
+        .set("formality", "more")
+        .set("tag_handling", "xml");
+    let translator = DeepLTranslator::from_config(&config).unwrap();
+    assert_eq!(translator.formality, Some(Formality::More));
+    assert_eq!(translator.tag_handling, Some(TagHandling::Xml));
+
+    let err = DeepLTranslator::from_config(&BackendConfig::new()).unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::Builder);
+    let config = config.set("formality", "casual");
+    let err = DeepLTranslator::from_config(&config).unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::Builder);
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn test_translate_async() {
+    let transport = FakeTransport::new(deepl);
+    let translator = translator(transport.clone());
+
+    let res = futures::executor::block_on(translator.translate_async("Hello", "", "de")).unwrap();
+    assert_eq!(res, "[DE]Hello");
+    let res =
+        futures::executor::block_on(translator.translate_batch_async(&["One", "Two"], "en", "fr"))
+            .unwrap();
+    assert_eq!(res, ["[FR]One", "[FR]Two"]);
+    let usage = futures::executor::block_on(translator.usage_async()).unwrap();
+    assert_eq!(usage.character_limit, 500000);
+}
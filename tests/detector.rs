@@ -0,0 +1,27 @@
+#![allow(clippy::assertions_on_constants)]
+
+use translators::{LanguageDetector, OfflineDetector};
+
+#[test]
+fn test_offline_detect() {
+    let detector = OfflineDetector;
+    let text = "Ceci est un texte écrit en français, il est assez long pour être détecté.";
+
+    match detector.detect_sync(text) {
+        Ok(detection) => {
+            assert_eq!(detection.language, "fr");
+            assert!(detection.confidence > 0.5);
+        }
+        Err(err) => {
+            eprintln!("Offline detection error: {:?}", err);
+            assert!(false);
+        }
+    }
+}
+
+#[test]
+fn test_offline_detect_empty() {
+    let detector = OfflineDetector;
+
+    assert!(detector.detect_sync("").is_err());
+}
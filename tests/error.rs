@@ -0,0 +1,52 @@
+use std::error::Error as _;
+use std::time::Duration;
+
+use translators::{Error, ErrorKind};
+
+#[test]
+fn test_error_details() {
+    let retry_after = Some(Duration::from_secs(30));
+    let err = Error::new(ErrorKind::RateLimited { retry_after }, "too many requests")
+        .with_status(429)
+        .with_chunk(2, 10..20)
+        .with_backend("google");
+
+    assert_eq!(err.kind(), &ErrorKind::RateLimited { retry_after });
+    assert_eq!(err.status(), Some(429));
+    assert_eq!(err.chunk_index(), Some(2));
+    assert_eq!(err.chunk_range(), Some(10..20));
+    assert_eq!(err.backend(), Some("google"));
+    assert!(err.is_retryable());
+    assert_eq!(
+        err.to_string(),
+        "google: Rate limited, retry after 30s (status 429): too many requests [chunk 2, bytes 10..20]"
+    );
+}
+
+#[test]
+fn test_error_retryable() {
+    assert!(Error::new(ErrorKind::Status, "")
+        .with_status(503)
+        .is_retryable());
+    assert!(!Error::new(ErrorKind::Status, "")
+        .with_status(404)
+        .is_retryable());
+    assert!(Error::new(ErrorKind::Captcha, "").is_retryable());
+    assert!(!Error::new(ErrorKind::UnsupportedLanguage, "xx").is_retryable());
+    assert!(!Error::new(ErrorKind::QuotaExceeded, "")
+        .with_status(456)
+        .is_retryable());
+}
+
+#[test]
+fn test_error_source() {
+    let bytes = vec![0xff];
+    let utf8_err = std::str::from_utf8(&bytes).unwrap_err();
+    let err = Error::from(utf8_err);
+
+    assert_eq!(err.kind(), &ErrorKind::Encoding);
+    assert_eq!(
+        err.source().map(|source| source.to_string()),
+        Some(utf8_err.to_string())
+    );
+}
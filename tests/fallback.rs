@@ -0,0 +1,417 @@
+//! FallbackTranslator with fake translators.
+
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+#[cfg(feature = "async")]
+use std::task::Poll;
+use std::thread;
+use std::time::Duration;
+
+use translators::{
+    CircuitState, DynTranslator, Error, ErrorKind, FallbackTranslator, Language, ServedChunk,
+    Translator,
+};
+
+/// Prefixes the texts with its name, or fails with `error` for the texts containing
+/// `failing_text`.
+#[derive(Debug, Clone, Default)]
+struct FakeTranslator {
+    name: &'static str,
+    error: Option<ErrorKind>,
+    failing_text: &'static str,
+    /// Turns the errors off.
+    healed: Arc<AtomicBool>,
+    /// The async translations never finish.
+    #[cfg(feature = "async")]
+    hanging: Arc<AtomicBool>,
+    calls: Arc<AtomicUsize>,
+}
+
+impl FakeTranslator {
+    fn new(name: &'static str) -> Self {
+        FakeTranslator {
+            name,
+            ..FakeTranslator::default()
+        }
+    }
+
+    fn failing(name: &'static str, error: ErrorKind) -> Self {
+        FakeTranslator {
+            name,
+            error: Some(error),
+            ..FakeTranslator::default()
+        }
+    }
+
+    fn calls(&self) -> usize {
+        self.calls.load(Ordering::SeqCst)
+    }
+
+    fn translate(&self, text: &str) -> Result<String, Error> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        match &self.error {
+            Some(kind)
+                if text.contains(self.failing_text) && !self.healed.load(Ordering::SeqCst) =>
+            {
+                Err(Error::new(kind.clone(), text).with_backend(self.name))
+            }
+            _ => Ok(format!("[{}]{}", self.name, text)),
+        }
+    }
+}
+
+impl Translator for FakeTranslator {
+    #[cfg(feature = "async")]
+    async fn translate_async(&self, text: &str, _: &str, _: &str) -> Result<String, Error> {
+        if self.hanging.load(Ordering::SeqCst) {
+            futures::future::pending::<()>().await;
+        }
+        self.translate(text)
+    }
+
+    fn translate_sync(&self, text: &str, _: &str, _: &str) -> Result<String, Error> {
+        self.translate(text)
+    }
+
+    fn supported_languages(&self) -> Vec<Language> {
+        vec![
+            Language {
+                code: "en".to_string(),
+                name: "English".to_string(),
+            },
+            Language {
+                code: self.name.to_string(),
+                name: self.name.to_string(),
+            },
+        ]
+    }
+}
+
+fn served(chunks: &[ServedChunk]) -> Vec<&str> {
+    chunks.iter().map(|chunk| chunk.backend.as_str()).collect()
+}
+
+#[test]
+fn test_first_backend() {
+    let google = FakeTranslator::new("google");
+    let bing = FakeTranslator::new("bing");
+    let translator = FallbackTranslator::new()
+        .backend("google", google.clone())
+        .backend("bing", bing.clone());
+
+    let translation = translator
+        .translate_detailed_sync("Hello", "en", "fr")
+        .unwrap();
+    assert_eq!(translation.text, "[google]Hello");
+    assert_eq!(
+        translation.chunks,
+        [ServedChunk {
+            range: 0..5,
+            backend: "google".to_string()
+        }]
+    );
+    assert_eq!(bing.calls(), 0);
+    assert_eq!(
+        translator.translate_sync("Hi", "en", "fr").unwrap(),
+        "[google]Hi"
+    );
+}
+
+#[test]
+fn test_fallback_per_chunk() {
+    let google = FakeTranslator {
+        failing_text: "Two",
+        ..FakeTranslator::failing("google", ErrorKind::Captcha)
+    };
+    let translator = FallbackTranslator::new()
+        .with_text_limit(5)
+        .backend("google", google)
+        .backend("bing", FakeTranslator::new("bing"));
+
+    let translation = translator
+        .translate_detailed_sync("One. Two. Six.", "en", "fr")
+        .unwrap();
+    assert_eq!(translation.text, "[google]One. [bing]Two. [google]Six.");
+    assert_eq!(served(&translation.chunks), ["google", "bing", "google"]);
+    assert_eq!(translation.chunks[1].range, 5..10);
+}
+
+#[test]
+fn test_fallback_errors() {
+    for kind in [
+        ErrorKind::RateLimited { retry_after: None },
+        ErrorKind::QuotaExceeded,
+        ErrorKind::UnsupportedLanguage,
+    ] {
+        let translator = FallbackTranslator::new()
+            .backend("deepl", FakeTranslator::failing("deepl", kind))
+            .backend("libre", FakeTranslator::new("libre"));
+        let res = translator.translate_sync("Hello", "en", "fr").unwrap();
+        assert_eq!(res, "[libre]Hello");
+    }
+
+    // the request itself is wrong, the other translators would fail too
+    let libre = FakeTranslator::new("libre");
+    let translator = FallbackTranslator::new()
+        .with_text_limit(5)
+        .backend(
+            "deepl",
+            FakeTranslator {
+                failing_text: "Two",
+                ..FakeTranslator::failing("deepl", ErrorKind::InvalidRequest)
+            },
+        )
+        .backend("libre", libre.clone());
+    let err = translator
+        .translate_sync("One. Two.", "en", "fr")
+        .unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::InvalidRequest);
+    assert_eq!(err.backend(), Some("deepl"));
+    assert_eq!(err.chunk_index(), Some(1));
+    assert_eq!(libre.calls(), 0);
+
+    // the last error when all of them fail
+    let translator = FallbackTranslator::new()
+        .backend(
+            "google",
+            FakeTranslator::failing("google", ErrorKind::Captcha),
+        )
+        .backend("bing", FakeTranslator::failing("bing", ErrorKind::Timeout));
+    let err = translator.translate_sync("Hello", "en", "fr").unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::Timeout);
+    assert_eq!(err.backend(), Some("bing"));
+
+    let err = FallbackTranslator::default()
+        .translate_sync("Hello", "en", "fr")
+        .unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::Builder);
+}
+
+#[test]
+fn test_circuit_demotes_backend() {
+    let google = FakeTranslator::failing("google", ErrorKind::Captcha);
+    let bing = FakeTranslator::new("bing");
+    let translator = FallbackTranslator::new()
+        .with_text_limit(5)
+        .backend("google", google.clone())
+        .backend("bing", bing.clone())
+        .with_circuit_breaker(2, Duration::from_millis(50));
+
+    let translation = translator
+        .translate_detailed_sync("One. Two. Six. Ten.", "en", "fr")
+        .unwrap();
+    assert_eq!(served(&translation.chunks), ["bing"; 4]);
+    // skipped after 2 failures
+    assert_eq!(google.calls(), 2);
+    assert_eq!(bing.calls(), 4);
+    let breaker = translator.backends()[0].breaker();
+    assert_eq!(translator.backends()[0].name(), "google");
+    assert_eq!(breaker.state(), CircuitState::Open);
+
+    // clones share the circuits
+    translator
+        .clone()
+        .translate_sync("Hello", "en", "fr")
+        .unwrap();
+    assert_eq!(google.calls(), 2);
+
+    // a probe after the cooldown, google is back
+    google.healed.store(true, Ordering::SeqCst);
+    thread::sleep(Duration::from_millis(60));
+    assert_eq!(breaker.state(), CircuitState::HalfOpen);
+    let translation = translator
+        .translate_detailed_sync("One. Two.", "en", "fr")
+        .unwrap();
+    assert_eq!(served(&translation.chunks), ["google", "google"]);
+    assert_eq!(breaker.state(), CircuitState::Closed);
+}
+
+#[test]
+fn test_circuit_local_errors() {
+    let google = FakeTranslator::failing("google", ErrorKind::UnsupportedLanguage);
+    let translator = FallbackTranslator::new()
+        .backend("google", google.clone())
+        .backend("bing", FakeTranslator::new("bing"))
+        .with_circuit_breaker(1, Duration::from_millis(20));
+    let breaker = translator.backends()[0].breaker();
+    breaker.try_acquire().unwrap().record_failure();
+    thread::sleep(Duration::from_millis(30));
+
+    // the probe fails without an answer of google, the circuit isn't closed
+    let res = translator.translate_sync("Hello", "en", "fr").unwrap();
+    assert_eq!(res, "[bing]Hello");
+    assert_eq!(google.calls(), 1);
+    assert_eq!(breaker.state(), CircuitState::HalfOpen);
+
+    google.healed.store(true, Ordering::SeqCst);
+    let res = translator.translate_sync("Hello", "en", "fr").unwrap();
+    assert_eq!(res, "[google]Hello");
+    assert_eq!(breaker.state(), CircuitState::Closed);
+}
+
+#[test]
+fn test_all_circuits_open() {
+    let translator = FallbackTranslator::new()
+        .with_circuit_breaker(1, Duration::from_secs(60))
+        .backend(
+            "google",
+            FakeTranslator::failing("google", ErrorKind::Captcha),
+        );
+
+    let err = translator.translate_sync("Hello", "en", "fr").unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::Captcha);
+    let err = translator.translate_sync("Hello", "en", "fr").unwrap_err();
+    assert!(matches!(
+        err.kind(),
+        ErrorKind::CircuitOpen {
+            retry_after: Some(_)
+        }
+    ));
+    assert_eq!(err.backend(), Some("fallback"));
+    assert!(err.is_retryable());
+}
+
+#[test]
+fn test_dyn_backends() {
+    let google: Box<dyn DynTranslator> = Box::new(FakeTranslator::new("google"));
+    let bing: Arc<dyn DynTranslator> = Arc::new(FakeTranslator::new("bing"));
+    let translator = FallbackTranslator::new()
+        .dyn_backend("google", google)
+        .dyn_backend("bing", bing);
+
+    let codes: Vec<_> = translator
+        .supported_languages()
+        .into_iter()
+        .map(|language| language.code)
+        .collect();
+    assert_eq!(codes, ["en", "google", "bing"]);
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn test_translate_async() {
+    let translator = FallbackTranslator::new()
+        .with_text_limit(5)
+        .backend(
+            "google",
+            FakeTranslator {
+                failing_text: "One",
+                ..FakeTranslator::failing("google", ErrorKind::ConnectFailed)
+            },
+        )
+        .backend("bing", FakeTranslator::new("bing"));
+
+    let translation =
+        futures::executor::block_on(translator.translate_detailed_async("One. Two.", "en", "fr"))
+            .unwrap();
+    assert_eq!(translation.text, "[bing]One. [google]Two.");
+    assert_eq!(served(&translation.chunks), ["bing", "google"]);
+    let res = futures::executor::block_on(translator.translate_async("Six.", "en", "fr")).unwrap();
+    assert_eq!(res, "[google]Six.");
+}
+
+/// Translates a chunk once `expected` chunks are being translated at once, fails after
+/// a while otherwise.
+#[cfg(feature = "async")]
+#[derive(Debug, Clone, Default)]
+struct Gathering {
+    started: Arc<AtomicUsize>,
+    expected: usize,
+}
+
+#[cfg(feature = "async")]
+impl Translator for Gathering {
+    async fn translate_async(&self, text: &str, _: &str, _: &str) -> Result<String, Error> {
+        self.started.fetch_add(1, Ordering::SeqCst);
+        for _ in 0..1000 {
+            if self.started.load(Ordering::SeqCst) >= self.expected {
+                return Ok(format!("[gathering]{text}"));
+            }
+            yield_now().await;
+        }
+        Err(Error::new(ErrorKind::Timeout, text))
+    }
+
+    fn translate_sync(&self, text: &str, _: &str, _: &str) -> Result<String, Error> {
+        Ok(format!("[gathering]{text}"))
+    }
+
+    fn supported_languages(&self) -> Vec<Language> {
+        Vec::new()
+    }
+}
+
+/// Lets the other futures run.
+#[cfg(feature = "async")]
+async fn yield_now() {
+    let mut yielded = false;
+    futures::future::poll_fn(|cx| {
+        if std::mem::replace(&mut yielded, true) {
+            return Poll::Ready(());
+        }
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    })
+    .await;
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn test_translate_async_concurrently() {
+    let gathering = Gathering {
+        started: Arc::default(),
+        expected: 3,
+    };
+    let translator = FallbackTranslator::new()
+        .with_text_limit(5)
+        .backend("gathering", gathering.clone());
+    let translation = futures::executor::block_on(translator.translate_detailed_async(
+        "One. Two. Six.",
+        "en",
+        "fr",
+    ))
+    .unwrap();
+    assert_eq!(
+        translation.text,
+        "[gathering]One. [gathering]Two. [gathering]Six."
+    );
+    assert_eq!(served(&translation.chunks), ["gathering"; 3]);
+
+    // 2 chunks at once at most
+    gathering.started.store(0, Ordering::SeqCst);
+    let translator = translator.with_max_concurrency(2);
+    let err = futures::executor::block_on(translator.translate_async("One. Two. Six.", "en", "fr"))
+        .unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::Timeout);
+    assert_eq!(err.chunk_index(), Some(0));
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn test_cancelled_probe() {
+    use futures::FutureExt;
+
+    let google = FakeTranslator::failing("google", ErrorKind::Captcha);
+    let translator = FallbackTranslator::new()
+        .backend("google", google.clone())
+        .backend("bing", FakeTranslator::new("bing"))
+        .with_circuit_breaker(1, Duration::from_millis(20));
+    translator.translate_sync("Hello", "en", "fr").unwrap();
+    thread::sleep(Duration::from_millis(30));
+
+    // the probe is cancelled before its answer
+    google.healed.store(true, Ordering::SeqCst);
+    google.hanging.store(true, Ordering::SeqCst);
+    let mut probe = Box::pin(translator.translate_async("Hello", "en", "fr"));
+    assert!((&mut probe).now_or_never().is_none());
+    drop(probe);
+
+    // the next request probes google again
+    google.hanging.store(false, Ordering::SeqCst);
+    let res = futures::executor::block_on(translator.translate_async("Hello", "en", "fr"));
+    assert_eq!(res.unwrap(), "[google]Hello");
+    assert_eq!(
+        translator.backends()[0].breaker().state(),
+        CircuitState::Closed
+    );
+}
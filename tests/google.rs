@@ -3,12 +3,18 @@ use std::{
     time::{Duration, Instant},
 };
 
-use translators::{GoogleTranslator, Translator};
+mod common;
+
+use common::FakeTransport;
+use translators::{
+    BackendConfig, ErrorKind, GoogleTranslator, LanguageDetector, Response, Translator,
+};
 
 // "Hi"
 const TEXT_CHUNKS: u128 = 4;
 
 #[tokio::test]
+#[allow(clippy::assertions_on_constants)]
 async fn test_async() {
     let translator = GoogleTranslator::default();
     let text = "Hello, world!";
@@ -29,6 +35,7 @@ async fn test_async() {
     }
 }
 #[test]
+#[allow(clippy::assertions_on_constants)]
 fn test_sync() {
     let translator = GoogleTranslator::default();
     let text = "Hello, world!";
@@ -47,6 +54,7 @@ fn test_sync() {
 }
 
 #[tokio::test]
+#[allow(deprecated, clippy::assertions_on_constants)]
 async fn test_async_builder() {
     let translator = GoogleTranslator::builder()
         .timeout(35usize)
@@ -74,6 +82,7 @@ async fn test_async_builder() {
 }
 
 #[test]
+#[allow(deprecated, clippy::assertions_on_constants)]
 fn test_sync_builder() {
     let translator = GoogleTranslator::builder()
         .timeout(35usize)
@@ -98,6 +107,7 @@ fn test_sync_builder() {
 }
 
 #[tokio::test]
+#[allow(deprecated, clippy::assertions_on_constants)]
 async fn test_async_delay() {
     let (res_time, res_abs) = res_time().await;
     let translator = GoogleTranslator::builder()
@@ -131,6 +141,7 @@ async fn test_async_delay() {
 }
 
 #[tokio::test]
+#[allow(deprecated, clippy::assertions_on_constants)]
 async fn test_async_conc() {
     let (res_time, res_abs) = res_time().await;
     let translator = GoogleTranslator::builder()
@@ -162,6 +173,311 @@ async fn test_async_conc() {
     }
 }
 
+/// Answer of the JSON API for "Bonjour le monde!".
+fn detection() -> Response {
+    Response::new(
+        200,
+        "https://translate.googleapis.com/translate_a/single",
+        r#"[[["Hello world!","Bonjour le monde!",null,null,10]],null,"fr",null,null,null,0.98]"#,
+    )
+}
+
+#[tokio::test]
+async fn test_detect_async() {
+    let transport = FakeTransport::responding(detection());
+    let translator = GoogleTranslator::builder()
+        .transport(transport.clone())
+        .build();
+
+    let detection = translator.detect_async("Bonjour le monde!").await.unwrap();
+    assert_eq!(detection.language, "fr");
+    assert_eq!(detection.confidence, 0.98);
+    assert!(transport.urls()[0].starts_with("https://translate.googleapis.com/"));
+}
+
+#[test]
+fn test_detect_sync() {
+    let transport = FakeTransport::responding(detection());
+    let translator = GoogleTranslator::builder()
+        .transport(transport.clone())
+        .build();
+
+    let detection = translator.detect_sync("Bonjour le monde!").unwrap();
+    assert_eq!(detection.language, "fr");
+    assert!(transport.urls()[0].starts_with("https://translate.googleapis.com/"));
+}
+
+#[test]
+fn test_supported_languages() {
+    let translator = GoogleTranslator::default();
+    let languages = translator.supported_languages();
+
+    assert!(languages
+        .iter()
+        .any(|language| language.code == "fr" && language.name == "French"));
+}
+
+#[test]
+fn test_unsupported_language() {
+    let translator = GoogleTranslator::builder().validate_languages(true).build();
+
+    for (source_lang, target_lang) in [("en", "xx"), ("xx", "fr")] {
+        let err = translator
+            .translate_sync("Hello, world!", source_lang, target_lang)
+            .unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::UnsupportedLanguage);
+        assert_eq!(err.message(), "xx");
+        assert_eq!(err.backend(), Some("google"));
+        assert!(!err.is_retryable());
+    }
+
+    let config = BackendConfig::new().set("validate_languages", "true");
+    assert!(
+        GoogleTranslator::from_config(&config)
+            .unwrap()
+            .validate_languages
+    );
+}
+
+#[test]
+fn test_unlisted_language() {
+    let transport = FakeTransport::responding(Response::new(
+        200,
+        "https://translate.google.com/m",
+        r#"<div class="result-container">Olá</div>"#,
+    ));
+    let translator = GoogleTranslator::builder()
+        .transport(transport.clone())
+        .build();
+
+    for target_lang in ["pt-PT", "fr-CA", "yue", "sr-Latn"] {
+        let res = translator
+            .translate_sync("Hello", "en", target_lang)
+            .unwrap();
+        assert_eq!(res, "Olá");
+    }
+    assert_eq!(transport.requests().len(), 4);
+}
+
+#[test]
+fn test_malformed_language() {
+    let transport = FakeTransport::failing(500, "");
+    let translator = GoogleTranslator::builder()
+        .transport(transport.clone())
+        .build();
+
+    for (source_lang, target_lang) in [("en", ""), ("en", "auto"), ("en", "fr CA"), ("?", "fr")] {
+        let err = translator
+            .translate_sync("Hello", source_lang, target_lang)
+            .unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::UnsupportedLanguage);
+    }
+    assert!(transport.requests().is_empty());
+}
+
+#[test]
+fn test_skip_untranslatable() {
+    let translator = GoogleTranslator::builder().skip_same_language(true).build();
+
+    for text in [
+        "  \n\t",
+        "12 345,67 %",
+        "https://example.com/a?b=c",
+        "```\nfn main() {}\n```",
+        "    let x = 1;\n    println!(\"{x}\");\n",
+    ] {
+        assert_eq!(translator.translate_sync(text, "en", "fr").unwrap(), text);
+    }
+    // code without a block is only kept with an auto source language
+    let text = "fn main() { x(); }";
+    assert_eq!(translator.translate_sync(text, "auto", "fr").unwrap(), text);
+}
+
+/// Generic code can spawn the translation on a multi-threaded runtime.
+fn spawn_translation<T: Translator + 'static>(
+    translator: &T,
+    text: &str,
+) -> tokio::task::JoinHandle<Result<String, translators::Error>> {
+    tokio::spawn(translator.translate_async_owned(text.to_string(), "en".into(), "fr".into()))
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_spawn_generic() {
+    let translator = GoogleTranslator::builder().skip_same_language(true).build();
+
+    let handle = spawn_translation(&translator, "12 345");
+    assert_eq!(handle.await.unwrap().unwrap(), "12 345");
+}
+
+#[cfg(feature = "offline-detect")]
+#[tokio::test]
+async fn test_skip_same_language() {
+    let translator = GoogleTranslator::builder()
+        .skip_same_language(true)
+        .text_limit(60usize)
+        .build();
+    let text = "Ceci est un texte qui est déjà écrit en français. \
+                Il ne faut pas le traduire une seconde fois, merci.";
+
+    assert_eq!(translator.translate_sync(text, "", "fr").unwrap(), text);
+    assert_eq!(
+        translator
+            .translate_async(text, "auto", "fr")
+            .await
+            .unwrap(),
+        text
+    );
+}
+
+#[test]
+fn test_total_timeout() {
+    let translator = GoogleTranslator::builder()
+        .total_timeout(Duration::ZERO)
+        .build();
+
+    let err = translator
+        .translate_sync("Hello, world!", "en", "fr")
+        .unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::Timeout);
+    assert!(err.is_retryable());
+}
+
+#[tokio::test]
+async fn test_blocking_inside_runtime() {
+    // refused connection, the request fails fast without network
+    let translator = GoogleTranslator::builder()
+        .proxy_address("http://127.0.0.1:1")
+        .build();
+
+    let err = translator
+        .translate_sync("Hello, world!", "en", "fr")
+        .unwrap_err();
+    assert_eq!(err.backend(), Some("google"));
+}
+
+#[tokio::test]
+async fn test_blocking_in_async() {
+    let translator = GoogleTranslator::builder().skip_same_language(true).build();
+
+    let result = translator
+        .translate_blocking_in_async("12 345".into(), "en".into(), "fr".into())
+        .await
+        .unwrap();
+    assert_eq!(result, "12 345");
+}
+
+#[test]
+fn test_parallel_sync_order() {
+    let translator = GoogleTranslator::builder()
+        .skip_same_language(true)
+        .max_concurrency(3usize)
+        .text_limit(2usize)
+        .build();
+    let text = "1 2 3 4 5 6 7 8 9 ";
+
+    assert_eq!(translator.translate_sync(text, "en", "fr").unwrap(), text);
+}
+
+#[test]
+fn test_parallel_sync_first_error() {
+    // refused connection, every chunk fails fast without network
+    let translator = GoogleTranslator::builder()
+        .proxy_address("http://127.0.0.1:1")
+        .max_concurrency(3usize)
+        .text_limit(2usize)
+        .build();
+
+    let err = translator
+        .translate_sync(&"Hi".repeat(TEXT_CHUNKS as usize), "en", "fr")
+        .unwrap_err();
+    assert_eq!(err.chunk_index(), Some(0));
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_deprecated_builder() {
+    let translator = GoogleTranslator::builder().timeout(20).delay(500).build();
+
+    assert_eq!(translator.request_timeout, Duration::from_secs(20));
+    assert_eq!(translator.request_delay, Duration::from_millis(500));
+}
+
+#[tokio::test]
+async fn test_fetch_languages() {
+    let transport = FakeTransport::responding(Response::new(
+        200,
+        "https://translate.google.com/m?mui=tl&hl=en",
+        r#"<div class="language-item"><a href="./m?sl=auto&amp;tl=fr&amp;hl=en">French</a></div>
+<div class="language-item"><a href="./m?sl=auto&amp;tl=zh-CN&amp;hl=en">Chinese (Simplified)</a></div>"#,
+    ));
+    let translator = GoogleTranslator::builder()
+        .transport(transport.clone())
+        .build();
+
+    let languages = translator.fetch_languages_async("en").await.unwrap();
+    assert_eq!(languages.len(), 2);
+    assert_eq!(languages[0].code, "fr");
+    assert_eq!(languages[1].name, "Chinese (Simplified)");
+    assert_eq!(
+        transport.urls(),
+        ["https://translate.google.com/m?mui=tl&hl=en"]
+    );
+}
+
+/// Result page of the translation of every chunk.
+fn translated() -> Response {
+    Response::new(
+        200,
+        "https://translate.google.com/m",
+        r#"<div class="result-container">Salut</div>"#,
+    )
+}
+
+#[test]
+fn test_duration_builder() {
+    let transport = FakeTransport::responding(translated());
+    let translator = GoogleTranslator::builder()
+        .request_timeout(Duration::from_secs(35))
+        .connect_timeout(Duration::from_secs(5))
+        .request_delay(Duration::from_millis(100))
+        .text_limit(2usize)
+        .transport(transport.clone())
+        .build();
+    let start_time = Instant::now();
+
+    translator.translate_sync("Hi Hi Hi", "en", "fr").unwrap();
+    let requests = transport.requests();
+    assert!(requests.len() >= 3);
+    assert!(start_time.elapsed() >= Duration::from_millis(100) * (requests.len() as u32 - 1));
+    for request in requests {
+        assert_eq!(request.timeout, Duration::from_secs(35));
+        assert_eq!(request.connect_timeout, Some(Duration::from_secs(5)));
+    }
+}
+
+#[tokio::test]
+async fn test_duration_builder_async() {
+    let transport = FakeTransport::responding(translated());
+    let translator = GoogleTranslator::builder()
+        .request_timeout(Duration::from_secs(35))
+        .request_delay(Duration::from_millis(100))
+        .text_limit(2usize)
+        .transport(transport.clone())
+        .build();
+    let start_time = Instant::now();
+
+    translator
+        .translate_async("Hi Hi Hi", "en", "fr")
+        .await
+        .unwrap();
+    let requests = transport.requests();
+    assert!(requests.len() >= 3);
+    assert!(start_time.elapsed() >= Duration::from_millis(100) * (requests.len() as u32 - 1));
+    assert!(requests
+        .iter()
+        .all(|request| request.timeout == Duration::from_secs(35)));
+}
+
 async fn res_time() -> (Duration, Duration) {
     let translator = GoogleTranslator::default();
     let text = "HiHiHiHi";
@@ -199,6 +515,7 @@ async fn res_time() -> (Duration, Duration) {
 }
 
 #[tokio::test]
+#[allow(deprecated, clippy::assertions_on_constants)]
 async fn test_file() {
     let translator = GoogleTranslator::builder()
         .timeout(35usize)
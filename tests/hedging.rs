@@ -0,0 +1,107 @@
+use std::thread;
+use std::time::Duration;
+
+use futures::executor::block_on;
+use futures::future;
+use translators::Hedging;
+
+/// Answers after `delay`, without a timer.
+async fn answer(value: &'static str, delay: Duration) -> Result<&'static str, &'static str> {
+    let (sender, receiver) = futures::channel::oneshot::channel();
+    thread::spawn(move || {
+        thread::sleep(delay);
+        let _ = sender.send(());
+    });
+    receiver.await.unwrap();
+    Ok(value)
+}
+
+#[test]
+fn test_hedge_slow_request() {
+    block_on(async {
+        let hedging = Hedging::new(Duration::from_millis(20)).with_budget(1.0);
+
+        let res = hedging
+            .race(future::pending(), || answer("hedge", Duration::ZERO))
+            .await;
+        assert_eq!(res, Ok("hedge"));
+        assert_eq!(hedging.hedged(), 1);
+
+        // fast enough, no duplicate
+        let res = hedging
+            .race(answer("first", Duration::ZERO), || {
+                answer("hedge", Duration::ZERO)
+            })
+            .await;
+        assert_eq!(res, Ok("first"));
+        assert_eq!(hedging.hedged(), 1);
+    });
+}
+
+#[test]
+fn test_hedge_errors() {
+    block_on(async {
+        let hedging = Hedging::new(Duration::from_millis(10)).with_budget(1.0);
+
+        // the first success wins, even if it is the slowest
+        let slow = answer("first", Duration::from_millis(50));
+        let res = hedging.race(slow, || future::ready(Err("captcha"))).await;
+        assert_eq!(res, Ok("first"));
+
+        let slow = async {
+            answer("", Duration::from_millis(50)).await?;
+            Err::<&str, _>("timeout")
+        };
+        let res = hedging.race(slow, || future::ready(Err("captcha"))).await;
+        assert_eq!(res, Err("timeout"));
+    });
+}
+
+#[test]
+fn test_hedging_budget() {
+    block_on(async {
+        let hedging = Hedging::new(Duration::from_millis(10)).with_budget(0.5);
+
+        for _ in 0..4 {
+            let res = hedging
+                .race(answer("first", Duration::from_millis(30)), || {
+                    answer("hedge", Duration::ZERO)
+                })
+                .await;
+            assert!(res.is_ok());
+        }
+        // 1 duplicate for 2 requests
+        assert_eq!(hedging.hedged(), 2);
+
+        let hedging = Hedging::new(Duration::ZERO).with_budget(0.0);
+        let res = hedging
+            .race(answer("first", Duration::from_millis(10)), || {
+                answer("hedge", Duration::ZERO)
+            })
+            .await;
+        assert_eq!(res, Ok("first"));
+        assert_eq!(hedging.hedged(), 0);
+    });
+}
+
+#[test]
+fn test_hedging_percentile() {
+    block_on(async {
+        let hedging = Hedging::new(Duration::from_millis(1)).with_percentile(0.9);
+        assert_eq!(hedging.delay(), Duration::from_millis(1));
+
+        let clone = hedging.clone();
+        for _ in 0..20 {
+            // blocks in the first poll, before the delay is checked
+            let request = async {
+                thread::sleep(Duration::from_millis(5));
+                Ok::<_, ()>(())
+            };
+            clone.race(request, || future::ready(Ok(()))).await.unwrap();
+        }
+        let delay = hedging.delay();
+        assert!(delay >= Duration::from_millis(5), "{delay:?}");
+        assert!(delay < Duration::from_millis(50), "{delay:?}");
+        assert_eq!(hedging.hedged(), 0);
+    });
+}
@@ -0,0 +1,177 @@
+//! LibreTranslate against a fake server.
+
+mod common;
+
+use common::FakeTransport;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use translators::{
+    BackendConfig, ErrorKind, LanguageDetector, LibreTranslator, Method, Request, Response,
+    Translator, TranslatorRegistry,
+};
+
+/// Answers like a LibreTranslate server, `/translate` prefixes the text with the target.
+fn libre(request: &Request) -> Response {
+    let body: Value = request
+        .body
+        .as_deref()
+        .map_or(Value::Null, |body| serde_json::from_str(body).unwrap());
+    let answer = match request.url.rsplit('/').next().unwrap() {
+        "translate" => json!({
+            "translatedText": format!("[{}]{}", body["target"].as_str().unwrap(), body["q"].as_str().unwrap()),
+        }),
+        "detect" => json!([
+            {"confidence": 90.0, "language": "fr"},
+            {"confidence": 10.0, "language": "it"},
+        ]),
+        "languages" => json!([
+            {"code": "en", "name": "English", "targets": ["fr"]},
+            {"code": "fr", "name": "French", "targets": ["en"]},
+        ]),
+        _ => return Response::new(404, &request.url, ""),
+    };
+    Response::new(200, &request.url, answer.to_string())
+}
+
+fn translator(transport: Arc<FakeTransport>) -> LibreTranslator {
+    LibreTranslator::builder()
+        .url("http://libre.local:5000/")
+        .api_key("secret")
+        .transport(transport)
+        .build()
+}
+
+#[test]
+fn test_translate() {
+    let transport = FakeTransport::new(libre);
+    let res = translator(transport.clone())
+        .translate_sync("Hello", "", "fr")
+        .unwrap();
+    assert_eq!(res, "[fr]Hello");
+
+    let requests = transport.requests();
+    assert_eq!(requests[0].method, Method::Post);
+    assert_eq!(requests[0].url, "http://libre.local:5000/translate");
+    assert!(requests[0]
+        .headers
+        .contains(&("Content-Type".to_string(), "application/json".to_string())));
+    drop(requests);
+    assert_eq!(
+        transport.bodies()[0],
+        json!({"q": "Hello", "source": "auto", "target": "fr", "format": "text", "api_key": "secret"})
+    );
+}
+
+#[test]
+fn test_translate_chunks() {
+    let transport = FakeTransport::new(libre);
+    let translator = LibreTranslator::builder()
+        .text_limit(6usize)
+        .max_concurrency(3usize)
+        .transport(transport.clone())
+        .build();
+
+    let res = translator
+        .translate_sync("Hello world again", "en", "fr")
+        .unwrap();
+    assert_eq!(res, "[fr]Hello [fr]world [fr]again");
+    let bodies = transport.bodies();
+    assert_eq!(bodies.len(), 3);
+    assert!(bodies.iter().all(|body| body.get("api_key").is_none()));
+    assert!(bodies.iter().all(|body| body["source"] == "en"));
+}
+
+#[test]
+fn test_errors() {
+    let transport = FakeTransport::failing(400, r#"{"error": "fr is not supported"}"#);
+    let err = translator(transport)
+        .translate_sync("Hello", "en", "fr")
+        .unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::InvalidRequest);
+    assert_eq!(err.message(), "fr is not supported");
+    assert_eq!(err.status(), Some(400));
+    assert_eq!(err.backend(), Some("libre"));
+    assert_eq!(err.chunk_index(), Some(0));
+
+    let transport = FakeTransport::failing(403, r#"{"error": "Invalid API key"}"#);
+    let err = translator(transport)
+        .translate_sync("Hello", "en", "fr")
+        .unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::Status);
+    assert_eq!(err.status(), Some(403));
+
+    let transport = FakeTransport::failing(429, r#"{"error": "Slowdown"}"#);
+    let err = translator(transport)
+        .translate_sync("Hello", "en", "fr")
+        .unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::RateLimited { .. }));
+    assert!(err.is_retryable());
+
+    let transport = FakeTransport::failing(200, "<html>");
+    let err = translator(transport)
+        .translate_sync("Hello", "en", "fr")
+        .unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::DecodeBody);
+
+    let err = translator(FakeTransport::new(libre))
+        .translate_sync("Hello", "en", "")
+        .unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::UnsupportedLanguage);
+}
+
+#[test]
+fn test_detect() {
+    let transport = FakeTransport::new(libre);
+    let detection = translator(transport.clone())
+        .detect_sync("Bonjour le monde")
+        .unwrap();
+    assert_eq!(detection.language, "fr");
+    assert_eq!(detection.confidence, 0.9);
+    assert_eq!(
+        transport.bodies()[0],
+        json!({"q": "Bonjour le monde", "api_key": "secret"})
+    );
+}
+
+#[test]
+fn test_fetch_languages() {
+    let transport = FakeTransport::new(libre);
+    let languages = translator(transport.clone())
+        .fetch_languages_sync()
+        .unwrap();
+    assert_eq!(languages.len(), 2);
+    assert_eq!(languages[1].code, "fr");
+    assert_eq!(languages[1].name, "French");
+    let requests = transport.requests();
+    assert_eq!(requests[0].method, Method::Get);
+    assert_eq!(requests[0].url, "http://libre.local:5000/languages");
+}
+
+#[test]
+fn test_registry() {
+    let config = BackendConfig::new()
+        .set("url", "http://libre.local")
+        .set("api_key", "secret")
+        .set("text_limit", "100");
+    let translator = LibreTranslator::from_config(&config).unwrap();
+    assert_eq!(translator.url, "http://libre.local");
+    assert_eq!(translator.api_key.as_deref(), Some("secret"));
+    assert_eq!(translator.text_limit, 100);
+    assert!(TranslatorRegistry::default().names().contains(&"libre"));
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn test_translate_async() {
+    let transport = FakeTransport::new(libre);
+    let translator = LibreTranslator::builder()
+        .text_limit(6usize)
+        .transport(transport.clone())
+        .build();
+
+    let res =
+        futures::executor::block_on(translator.translate_async("Hello world", "en", "de")).unwrap();
+    assert_eq!(res, "[de]Hello [de]world");
+    let detection = futures::executor::block_on(translator.detect_async("Bonjour")).unwrap();
+    assert_eq!(detection.language, "fr");
+}
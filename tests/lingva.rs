@@ -0,0 +1,128 @@
+//! Lingva against a fake instance.
+
+mod common;
+
+use common::FakeTransport;
+use serde_json::json;
+use std::sync::Arc;
+use translators::{
+    BackendConfig, ErrorKind, LingvaTranslator, Method, Request, Response, Translator,
+};
+
+/// Answers like a Lingva instance, the translations prefix the texts with the target.
+fn lingva(request: &Request) -> Response {
+    let path = request.url.split_once("/api/v1/").unwrap().1;
+    let answer = if path == "languages/target" {
+        json!({"languages": [
+            {"code": "en", "name": "English"},
+            {"code": "zh_HANT", "name": "Chinese (Traditional)"},
+        ]})
+    } else {
+        let parts: Vec<_> = path.splitn(3, '/').collect();
+        let text = urlencoding::decode(parts[2]).unwrap();
+        json!({"translation": format!("[{}]{}", parts[1], text)})
+    };
+    Response::new(200, &request.url, answer.to_string())
+}
+
+fn translator(transport: Arc<FakeTransport>) -> LingvaTranslator {
+    LingvaTranslator::builder()
+        .url("http://lingva.local/")
+        .transport(transport)
+        .build()
+}
+
+#[test]
+fn test_translate() {
+    let transport = FakeTransport::new(lingva);
+    let translator = translator(transport.clone());
+
+    let res = translator
+        .translate_sync("Hello / world", "", "fr")
+        .unwrap();
+    assert_eq!(res, "[fr]Hello / world");
+    let res = translator.translate_sync("Hello", "en", "de").unwrap();
+    assert_eq!(res, "[de]Hello");
+
+    let requests = transport.requests();
+    assert_eq!(requests[0].method, Method::Get);
+    assert_eq!(
+        requests[0].url,
+        "http://lingva.local/api/v1/auto/fr/Hello%20%2F%20world"
+    );
+    assert_eq!(requests[1].url, "http://lingva.local/api/v1/en/de/Hello");
+}
+
+#[test]
+fn test_translate_chunks() {
+    let transport = FakeTransport::new(lingva);
+    let translator = LingvaTranslator {
+        text_limit: 6,
+        ..translator(transport.clone())
+    };
+
+    let res = translator
+        .translate_sync("Hello world", "en", "de")
+        .unwrap();
+    assert_eq!(res, "[de]Hello [de]world");
+    assert_eq!(transport.requests().len(), 2);
+}
+
+#[test]
+fn test_fetch_languages() {
+    let transport = FakeTransport::new(lingva);
+    let languages = translator(transport.clone())
+        .fetch_languages_sync()
+        .unwrap();
+    assert_eq!(languages.len(), 2);
+    assert_eq!(languages[1].code, "zh_HANT");
+    assert_eq!(
+        transport.requests()[0].url,
+        "http://lingva.local/api/v1/languages/target"
+    );
+}
+
+#[test]
+fn test_errors() {
+    let transport = FakeTransport::failing(400, r#"{"error": "Invalid target language"}"#);
+    let err = translator(transport)
+        .translate_sync("Hello", "en", "xx")
+        .unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::InvalidRequest);
+    assert_eq!(err.message(), "Invalid target language");
+    assert_eq!(err.status(), Some(400));
+    assert_eq!(err.backend(), Some("lingva"));
+    assert_eq!(err.chunk_index(), Some(0));
+
+    let transport = FakeTransport::failing(429, "");
+    let err = translator(transport)
+        .translate_sync("Hello", "en", "fr")
+        .unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::RateLimited { .. }));
+    assert!(err.is_retryable());
+
+    let transport = FakeTransport::failing(502, "Bad Gateway");
+    let err = translator(transport).fetch_languages_sync().unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::Status);
+    assert!(err.is_retryable());
+}
+
+#[test]
+fn test_from_config() {
+    let config = BackendConfig::new().set("url", "https://lingva.example.org");
+    let translator = LingvaTranslator::from_config(&config).unwrap();
+    assert_eq!(translator.url, "https://lingva.example.org");
+    assert_eq!(translator.text_limit, 1000);
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn test_translate_async() {
+    let transport = FakeTransport::new(lingva);
+    let translator = translator(transport);
+
+    let res = futures::executor::block_on(translator.translate_async("Hello", "", "de")).unwrap();
+    assert_eq!(res, "[de]Hello");
+    let languages = futures::executor::block_on(translator.fetch_languages_async()).unwrap();
+    assert_eq!(languages[0].code, "en");
+}
@@ -0,0 +1,258 @@
+//! LlmTranslator against a fake OpenAI-compatible server.
+
+mod common;
+
+use common::FakeTransport;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use translators::{BackendConfig, ErrorKind, LlmTranslator, Method, Request, Response, Translator};
+
+/// Answers like a chat completions server, the "translation" is the last message of the
+/// user in angle brackets.
+fn llm(request: &Request) -> Response {
+    completion(request, "stop")
+}
+
+/// Answer of [`llm`] with this `finish_reason`.
+fn completion(request: &Request, finish_reason: &str) -> Response {
+    let body: Value = serde_json::from_str(request.body.as_deref().unwrap()).unwrap();
+    let text = body["messages"].as_array().unwrap().last().unwrap()["content"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let answer = json!({
+        "id": "chatcmpl-1",
+        "object": "chat.completion",
+        "choices": [{
+            "index": 0,
+            // models often add whitespace around the answer
+            "message": {"role": "assistant", "content": format!("\n<{}> ", text)},
+            "finish_reason": finish_reason,
+        }],
+    });
+    Response::new(200, &request.url, answer.to_string())
+}
+
+fn translator(transport: Arc<FakeTransport>) -> LlmTranslator {
+    LlmTranslator::builder()
+        .url("http://llm.local/v1/")
+        .model("qwen2.5:7b")
+        .transport(transport)
+        .build()
+}
+
+/// `(role, content)` of the messages of a request.
+fn messages(body: &Value) -> Vec<(String, String)> {
+    body["messages"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|message| {
+            (
+                message["role"].as_str().unwrap().to_string(),
+                message["content"].as_str().unwrap().to_string(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_translate() {
+    let transport = FakeTransport::new(llm);
+    let translator = LlmTranslator {
+        api_key: Some("secret".to_string()),
+        ..translator(transport.clone())
+    };
+
+    let res = translator.translate_sync("Hello", "en", "fr").unwrap();
+    assert_eq!(res, "<Hello>");
+
+    let requests = transport.requests();
+    assert_eq!(requests[0].method, Method::Post);
+    assert_eq!(requests[0].url, "http://llm.local/v1/chat/completions");
+    assert!(requests[0]
+        .headers
+        .contains(&("Authorization".to_string(), "Bearer secret".to_string())));
+    drop(requests);
+    let body = &transport.bodies()[0];
+    assert_eq!(body["model"], "qwen2.5:7b");
+    assert_eq!(body["temperature"], 0.0);
+    let messages = messages(body);
+    assert_eq!(messages.len(), 2);
+    assert_eq!(messages[0].0, "system");
+    assert!(messages[0].1.contains("from English to French"));
+    assert_eq!(messages[1], ("user".to_string(), "Hello".to_string()));
+}
+
+#[test]
+fn test_system_prompt() {
+    let transport = FakeTransport::new(llm);
+    let translator = LlmTranslator {
+        system_prompt: "Translate {source} into {target}, formally.".to_string(),
+        model: String::new(),
+        temperature: None,
+        ..translator(transport.clone())
+    };
+
+    translator.translate_sync("Hello", "", "de").unwrap();
+    translator.translate_sync("Hello", "en", "tlh").unwrap();
+    let bodies = transport.bodies();
+    assert_eq!(
+        messages(&bodies[0])[0].1,
+        "Translate the language of the text into German, formally."
+    );
+    // unknown codes are sent as they are
+    assert_eq!(
+        messages(&bodies[1])[0].1,
+        "Translate English into tlh, formally."
+    );
+    // nothing sent for the server defaults
+    assert!(bodies[0].get("model").is_none());
+    assert!(bodies[0].get("temperature").is_none());
+}
+
+#[test]
+fn test_context_chunks() {
+    let transport = FakeTransport::new(llm);
+    let translator = LlmTranslator {
+        text_limit: 5,
+        ..translator(transport.clone())
+    };
+
+    let res = translator
+        .translate_sync("One. Two. Six. Ten.", "en", "fr")
+        .unwrap();
+    // the whitespace between the chunks is kept
+    assert_eq!(res, "<One.> <Two.> <Six.> <Ten.>");
+
+    let bodies = transport.bodies();
+    assert_eq!(bodies.len(), 4);
+    assert_eq!(messages(&bodies[0]).len(), 2);
+    assert_eq!(messages(&bodies[1]).len(), 4);
+    // the 2 previous chunks with their translations, the oldest first
+    let last = messages(&bodies[3]);
+    let turns: Vec<_> = last[1..]
+        .iter()
+        .map(|(role, content)| format!("{}: {}", role, content))
+        .collect();
+    assert_eq!(
+        turns,
+        [
+            "user: Two.",
+            "assistant: <Two.>",
+            "user: Six.",
+            "assistant: <Six.>",
+            "user: Ten.",
+        ]
+    );
+}
+
+#[test]
+fn test_without_context() {
+    let transport = FakeTransport::new(llm);
+    let translator = LlmTranslator {
+        text_limit: 5,
+        context_chunks: 0,
+        max_concurrency: Some(2usize),
+        ..translator(transport.clone())
+    };
+
+    let res = translator
+        .translate_sync("One. Two. Six.", "en", "fr")
+        .unwrap();
+    assert_eq!(res, "<One.> <Two.> <Six.>");
+    for body in transport.bodies() {
+        assert_eq!(messages(&body).len(), 2);
+    }
+}
+
+#[test]
+fn test_errors() {
+    let transport = FakeTransport::failing(
+        429,
+        r#"{"error": {"message": "You exceeded your current quota.", "type": "insufficient_quota", "code": "insufficient_quota"}}"#,
+    );
+    let err = translator(transport)
+        .translate_sync("Hello", "en", "fr")
+        .unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::QuotaExceeded);
+    assert_eq!(err.message(), "You exceeded your current quota.");
+    assert_eq!(err.backend(), Some("llm"));
+    assert!(!err.is_retryable());
+
+    let transport = FakeTransport::failing(429, r#"{"error": {"message": "Rate limit reached"}}"#);
+    let err = translator(transport)
+        .translate_sync("Hello", "en", "fr")
+        .unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::RateLimited { .. }));
+
+    // the error of some local servers is a string
+    let transport = FakeTransport::failing(
+        400,
+        r#"{"error": "the request exceeds the available context size"}"#,
+    );
+    let err = translator(transport)
+        .translate_sync("Hello", "en", "fr")
+        .unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::InvalidRequest);
+    assert_eq!(
+        err.message(),
+        "the request exceeds the available context size"
+    );
+    assert_eq!(err.chunk_index(), Some(0));
+
+    let transport = FakeTransport::new(|request| completion(request, "length"));
+    let err = translator(transport)
+        .translate_sync("Hello", "en", "fr")
+        .unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::InvalidRequest);
+
+    let err = translator(FakeTransport::new(llm))
+        .translate_sync("Hello", "en", "auto")
+        .unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::UnsupportedLanguage);
+}
+
+#[test]
+fn test_from_config() {
+    let config = BackendConfig::new()
+        .set("url", "http://localhost:8000/v1")
+        .set("model", "mistral-7b-instruct")
+        .set("temperature", "0.3")
+        .set("context_chunks", "4");
+    let translator = LlmTranslator::from_config(&config).unwrap();
+    assert_eq!(translator.url, "http://localhost:8000/v1");
+    assert_eq!(translator.model, "mistral-7b-instruct");
+    assert_eq!(translator.temperature, Some(0.3));
+    assert_eq!(translator.context_chunks, 4);
+
+    let config = BackendConfig::new().set("temperature", "warm");
+    let err = LlmTranslator::from_config(&config).unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::Builder);
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn test_translate_async() {
+    let transport = FakeTransport::new(llm);
+    let translator = LlmTranslator {
+        text_limit: 5,
+        context_chunks: 1,
+        ..translator(transport.clone())
+    };
+
+    let res = futures::executor::block_on(translator.translate_async("One. Two. Six.", "", "de"))
+        .unwrap();
+    assert_eq!(res, "<One.> <Two.> <Six.>");
+    let bodies = transport.bodies();
+    assert_eq!(messages(&bodies[2]).len(), 4);
+    assert_eq!(messages(&bodies[2])[1].1, "Two.");
+
+    let independent = LlmTranslator {
+        context_chunks: 0,
+        ..translator
+    };
+    let res =
+        futures::executor::block_on(independent.translate_async("One. Two.", "", "de")).unwrap();
+    assert_eq!(res, "<One.> <Two.>");
+}
@@ -0,0 +1,133 @@
+//! MyMemory against a fake API.
+
+mod common;
+
+use common::FakeTransport;
+use serde_json::json;
+use std::sync::Arc;
+use translators::{
+    BackendConfig, ErrorKind, Method, MyMemoryTranslator, Request, Response, Translator,
+};
+
+/// Answers like the MyMemory API, the translations prefix the texts with the language pair.
+fn mymemory(request: &Request) -> Response {
+    let query = request.url.split_once('?').unwrap().1;
+    let param = |name: &str| {
+        let value = query
+            .split('&')
+            .find_map(|pair| pair.strip_prefix(&format!("{}=", name)))
+            .unwrap();
+        urlencoding::decode(value).unwrap().into_owned()
+    };
+    let answer = json!({
+        "responseData": {
+            "translatedText": format!("[{}]{}", param("langpair"), param("q")),
+            "match": 1,
+        },
+        "quotaFinished": false,
+        "responseDetails": "",
+        "responseStatus": 200,
+    });
+    Response::new(200, &request.url, answer.to_string())
+}
+
+fn translator(transport: Arc<FakeTransport>) -> MyMemoryTranslator {
+    MyMemoryTranslator::builder()
+        .url("http://mymemory.local/")
+        .transport(transport)
+        .build()
+}
+
+#[test]
+fn test_translate() {
+    let transport = FakeTransport::new(mymemory);
+    let translator = MyMemoryTranslator {
+        email: Some("me@example.com".to_string()),
+        api_key: Some("secret".to_string()),
+        ..translator(transport.clone())
+    };
+
+    let res = translator.translate_sync("Hello", "en", "fr").unwrap();
+    assert_eq!(res, "[en|fr]Hello");
+    let res = translator.translate_sync("Hello", "", "fr").unwrap();
+    assert_eq!(res, "[autodetect|fr]Hello");
+
+    let requests = transport.requests();
+    assert_eq!(requests[0].method, Method::Get);
+    assert_eq!(
+        requests[0].url,
+        "http://mymemory.local/get?q=Hello&langpair=en%7Cfr&de=me%40example.com&key=secret"
+    );
+}
+
+#[test]
+fn test_translate_chunks() {
+    let transport = FakeTransport::new(mymemory);
+    let translator = MyMemoryTranslator {
+        text_limit: 6,
+        ..translator(transport.clone())
+    };
+
+    let res = translator
+        .translate_sync("Hello world", "en", "de")
+        .unwrap();
+    assert_eq!(res, "[en|de]Hello [en|de]world");
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 2);
+    // no email by default
+    assert!(!requests[0].url.contains("&de="));
+}
+
+#[test]
+fn test_errors() {
+    let transport = FakeTransport::failing(
+        429,
+        r#"{"responseData": {"translatedText": "MYMEMORY WARNING: YOU USED ALL AVAILABLE FREE TRANSLATIONS FOR TODAY."}, "quotaFinished": true, "responseDetails": "MYMEMORY WARNING: YOU USED ALL AVAILABLE FREE TRANSLATIONS FOR TODAY.", "responseStatus": 429}"#,
+    );
+    let err = translator(transport)
+        .translate_sync("Hello", "en", "fr")
+        .unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::QuotaExceeded);
+    assert_eq!(err.status(), Some(429));
+    assert_eq!(err.backend(), Some("mymemory"));
+    assert!(!err.is_retryable());
+
+    // the status of the body, as a string
+    let transport = FakeTransport::failing(
+        200,
+        r#"{"responseData": {"translatedText": null}, "responseDetails": "'XX' IS AN INVALID TARGET LANGUAGE . EXAMPLE: LANGPAIR=EN|IT USING 2 LETTER ISO OR RFC3066 LIKE ZH-CN. ALMOST ALL LANGUAGES SUPPORTED BUT SOME MAY HAVE NO CONTENT", "responseStatus": "403"}"#,
+    );
+    let err = translator(transport)
+        .translate_sync("Hello", "en", "xx")
+        .unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::InvalidRequest);
+    assert_eq!(err.status(), Some(403));
+    assert_eq!(err.chunk_index(), Some(0));
+
+    let transport = FakeTransport::failing(429, "");
+    let err = translator(transport)
+        .translate_sync("Hello", "en", "fr")
+        .unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::RateLimited { .. }));
+}
+
+#[test]
+fn test_from_config() {
+    let config = BackendConfig::new()
+        .set("email", "me@example.com")
+        .set("api_key", "secret");
+    let translator = MyMemoryTranslator::from_config(&config).unwrap();
+    assert_eq!(translator.email.as_deref(), Some("me@example.com"));
+    assert_eq!(translator.api_key.as_deref(), Some("secret"));
+    assert_eq!(translator.text_limit, 500);
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn test_translate_async() {
+    let transport = FakeTransport::new(mymemory);
+    let translator = translator(transport);
+
+    let res = futures::executor::block_on(translator.translate_async("Hello", "en", "de")).unwrap();
+    assert_eq!(res, "[en|de]Hello");
+}
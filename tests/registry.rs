@@ -0,0 +1,141 @@
+use translators::{
+    BackendConfig, DynTranslator, Error, ErrorKind, Language, Translator, TranslatorRegistry,
+};
+
+/// Translator that returns the text in upper case.
+#[derive(Clone, Debug, Default)]
+struct UpperTranslator;
+
+impl Translator for UpperTranslator {
+    #[cfg(feature = "async")]
+    async fn translate_async(
+        &self,
+        text: &str,
+        source_language: &str,
+        target_language: &str,
+    ) -> Result<String, Error> {
+        self.translate_sync(text, source_language, target_language)
+    }
+
+    fn translate_sync(
+        &self,
+        text: &str,
+        _source_language: &str,
+        _target_language: &str,
+    ) -> Result<String, Error> {
+        Ok(text.to_uppercase())
+    }
+
+    fn supported_languages(&self) -> Vec<Language> {
+        vec![Language {
+            code: "up".to_string(),
+            name: "Upper".to_string(),
+        }]
+    }
+}
+
+fn registry() -> TranslatorRegistry {
+    let mut registry = TranslatorRegistry::default();
+    registry.register("upper", |_config| Ok(Box::new(UpperTranslator)));
+    registry
+}
+
+#[test]
+fn test_registry_custom() {
+    let translator: Box<dyn DynTranslator> =
+        registry().build("upper", &BackendConfig::new()).unwrap();
+
+    assert_eq!(
+        translator.translate_sync_dyn("Hello", "en", "up").unwrap(),
+        "HELLO"
+    );
+    assert_eq!(translator.supported_languages_dyn()[0].code, "up");
+}
+
+#[cfg(feature = "tokio-async")]
+#[tokio::test]
+async fn test_dyn_async() {
+    use std::sync::Arc;
+
+    let translators: Vec<Arc<dyn DynTranslator>> = vec![Arc::new(UpperTranslator)];
+
+    for translator in translators {
+        assert_eq!(
+            translator
+                .translate_async_dyn("Hello", "en", "up")
+                .await
+                .unwrap(),
+            "HELLO"
+        );
+    }
+}
+
+#[test]
+fn test_registry_unknown() {
+    let err = registry()
+        .build("unknown", &BackendConfig::new())
+        .unwrap_err();
+
+    assert_eq!(err.kind(), &ErrorKind::Builder);
+}
+
+#[cfg(feature = "google")]
+#[test]
+fn test_registry_google() {
+    let registry = registry();
+    let mut names = vec!["google", "upper"];
+    if cfg!(feature = "bing") {
+        names.push("bing");
+    }
+    if cfg!(feature = "deepl") {
+        names.push("deepl");
+    }
+    if cfg!(feature = "libre") {
+        names.push("libre");
+    }
+    if cfg!(feature = "lingva") {
+        names.push("lingva");
+    }
+    if cfg!(feature = "llm") {
+        names.push("llm");
+    }
+    if cfg!(feature = "mymemory") {
+        names.push("mymemory");
+    }
+    if cfg!(feature = "yandex") {
+        names.push("yandex");
+    }
+    names.sort_unstable();
+    assert_eq!(registry.names(), names);
+
+    let config = BackendConfig::new()
+        .set("request_timeout", "10")
+        .set("total_timeout", "0")
+        .set("text_limit", "100");
+    let translator = registry.build("google", &config).unwrap();
+
+    assert!(!translator.supported_languages_dyn().is_empty());
+    // the total timeout is already exceeded, nothing is sent
+    let err = translator
+        .translate_sync_dyn("Hello, world!", "en", "fr")
+        .unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::Timeout);
+
+    let config = BackendConfig::new().set("text_limit", "many");
+    let err = registry.build("google", &config).unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::Builder);
+}
+
+#[test]
+fn test_config_list() {
+    let config = BackendConfig::new().set("local_addresses", "192.0.2.1, 2001:db8::1,");
+
+    let addresses: Vec<std::net::IpAddr> = config.list("local_addresses").unwrap().unwrap();
+    assert_eq!(addresses.len(), 2);
+    assert!(addresses[1].is_ipv6());
+    assert!(config.list::<u8>("missing").unwrap().is_none());
+    assert_eq!(
+        config.list::<u8>("local_addresses").unwrap_err().kind(),
+        &ErrorKind::Builder
+    );
+}
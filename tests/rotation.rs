@@ -0,0 +1,35 @@
+use std::time::Duration;
+
+use translators::RotationPool;
+
+#[test]
+fn test_rotation() {
+    let pool = RotationPool::new(vec!["a", "b", "c"]);
+
+    let items: Vec<_> = (0..4).map(|_| pool.next().unwrap()).collect();
+    assert_eq!(items, ["a", "b", "c", "a"]);
+    assert_eq!(RotationPool::<&str>::new(Vec::new()).next(), None);
+}
+
+#[test]
+fn test_quarantine() {
+    let pool = RotationPool::new(vec!["a", "b"]);
+    let clone = pool.clone();
+    clone.quarantine(&"a");
+
+    assert_eq!(pool.available(), 1);
+    assert!((0..4).all(|_| pool.next() == Some("b")));
+
+    // all in quarantine, the first one to leave it is used
+    pool.quarantine(&"b");
+    assert_eq!(pool.available(), 0);
+    assert_eq!(pool.next(), Some("a"));
+}
+
+#[test]
+fn test_quarantine_duration() {
+    let pool = RotationPool::new(vec!["a", "b"]).with_quarantine(Duration::ZERO);
+    pool.quarantine(&"a");
+
+    assert_eq!(pool.available(), 2);
+}
@@ -0,0 +1,47 @@
+//! Async translations without tokio, the timers work on any executor.
+
+use std::time::{Duration, Instant};
+
+use futures::executor::block_on;
+use translators::{GoogleTranslator, Translator};
+
+#[test]
+fn test_async_delay_without_tokio() {
+    let translator = GoogleTranslator::builder()
+        .skip_same_language(true)
+        .request_delay(Duration::from_millis(50))
+        .text_limit(2usize)
+        .build();
+    let text = "1 2 3 4 ";
+    let start_time = Instant::now();
+
+    let result = block_on(translator.translate_async(text, "en", "fr")).unwrap();
+    assert_eq!(result, text);
+    assert!(start_time.elapsed() >= Duration::from_millis(200));
+}
+
+// the requests of the `tokio-async` feature need a tokio runtime
+#[cfg(not(feature = "tokio-async"))]
+#[test]
+fn test_async_total_timeout_without_tokio() {
+    let translator = GoogleTranslator::builder()
+        .total_timeout(Duration::ZERO)
+        .build();
+
+    let err = block_on(translator.translate_async("Hello, world!", "en", "fr")).unwrap_err();
+    assert_eq!(err.kind(), &translators::ErrorKind::Timeout);
+}
+
+#[cfg(not(feature = "tokio-async"))]
+#[test]
+fn test_async_blocking_pool_without_tokio() {
+    // refused connection, the requests fail fast without network
+    let translator = GoogleTranslator::builder()
+        .proxy_address("http://127.0.0.1:1")
+        .build();
+
+    let requests = (0..20).map(|_| translator.translate_async("Hello", "en", "fr"));
+    for result in block_on(futures::future::join_all(requests)) {
+        assert_eq!(result.unwrap_err().backend(), Some("google"));
+    }
+}
@@ -0,0 +1,797 @@
+//! Same behaviour with the `reqwest` and the `ureq` transports, and a fake one.
+
+mod common;
+
+use std::net::IpAddr;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use common::FakeTransport;
+use translators::{
+    AdaptiveConcurrency, BackendConfig, CircuitBreaker, CircuitState, CookieJar, Error, ErrorKind,
+    GoogleTranslator, IpVersion, Method, Request, Response, TlsConfig, Translator, Transport,
+};
+#[cfg(feature = "async")]
+use translators::{BoxFuture, Hedging};
+
+fn translated(text: &str) -> Response {
+    Response::new(
+        200,
+        "https://translate.google.com/m",
+        format!(r#"<div class="result-container">{text}</div>"#),
+    )
+}
+
+#[test]
+fn test_fake_transport() {
+    let transport = FakeTransport::responding(translated("Bonjour"));
+    let translator = GoogleTranslator::builder()
+        .request_timeout(Duration::from_secs(5))
+        .proxy_address("socks5://127.0.0.1:9050")
+        .transport(transport.clone())
+        .build();
+
+    assert_eq!(
+        translator.translate_sync("Hello", "en", "fr").unwrap(),
+        "Bonjour"
+    );
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, Method::Get);
+    assert!(requests[0].url.contains("tl=fr&sl=en&q=Hello"));
+    assert_eq!(requests[0].timeout, Duration::from_secs(5));
+    assert_eq!(
+        requests[0].proxy_address.as_deref(),
+        Some("socks5://127.0.0.1:9050")
+    );
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn test_fake_transport_async() {
+    let transport = FakeTransport::responding(translated("Bonjour"));
+    let translator = GoogleTranslator::builder().transport(transport).build();
+
+    let result = futures::executor::block_on(translator.translate_async("Hello", "en", "fr"));
+    assert_eq!(result.unwrap(), "Bonjour");
+}
+
+#[test]
+fn test_fake_transport_errors() {
+    let captcha = Response::new(302, "https://www.google.com/sorry/index", "");
+    let mut rate_limited = Response::new(429, "https://translate.google.com/m", "");
+    rate_limited
+        .headers
+        .push(("Retry-After".to_string(), "30".to_string()));
+
+    for (response, kind) in [
+        (captcha, ErrorKind::Captcha),
+        (
+            rate_limited,
+            ErrorKind::RateLimited {
+                retry_after: Some(Duration::from_secs(30)),
+            },
+        ),
+    ] {
+        let translator = GoogleTranslator::builder()
+            .transport(FakeTransport::responding(response))
+            .build();
+
+        let err = translator.translate_sync("Hello", "en", "fr").unwrap_err();
+        assert_eq!(err.kind(), &kind);
+        assert!(err.is_retryable());
+    }
+}
+
+#[test]
+fn test_skip_keeps_prose() {
+    // parentheses, punctuation, formulas and prose between code blocks aren't code
+    for text in [
+        "Call me (maybe)",
+        "Hello (world).",
+        "See [1] (p. 2)",
+        "    Hello",
+        "```a``` and ```b```",
+        "Let x = 2; then f(x) = {x + 1}",
+    ] {
+        let transport = FakeTransport::responding(translated("Bonjour"));
+        let translator = GoogleTranslator::builder()
+            .skip_same_language(true)
+            .transport(transport.clone())
+            .build();
+
+        assert_eq!(
+            translator.translate_sync(text, "en", "fr").unwrap(),
+            "Bonjour"
+        );
+        assert_eq!(transport.requests().len(), 1, "{text}");
+    }
+}
+
+fn header<'a>(request: &'a Request, name: &str) -> Option<&'a str> {
+    request
+        .headers
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.as_str())
+}
+
+#[test]
+fn test_request_headers() {
+    let mut response = translated("Bonjour");
+    response.headers.push((
+        "set-cookie".to_string(),
+        "NID=1; domain=.google.com".to_string(),
+    ));
+    let transport = FakeTransport::responding(response);
+    let cookie_jar = CookieJar::google_consent();
+    let translator = GoogleTranslator::builder()
+        .headers(vec![(
+            "Referer".to_string(),
+            "https://translate.google.com/".to_string(),
+        )])
+        .user_agents(vec!["agent-a".to_string(), "agent-b".to_string()])
+        .accept_language("fr-FR,fr;q=0.9")
+        .cookie_jar(cookie_jar.clone())
+        .transport(transport.clone())
+        .build();
+
+    translator.translate_sync("Hello", "en", "fr").unwrap();
+    translator
+        .clone()
+        .translate_sync("Hello", "en", "fr")
+        .unwrap();
+
+    let requests = transport.requests();
+    for request in requests.iter() {
+        assert_eq!(
+            header(request, "referer"),
+            Some("https://translate.google.com/")
+        );
+        assert_eq!(header(request, "accept-language"), Some("fr-FR,fr;q=0.9"));
+        assert!(header(request, "cookie").unwrap().contains("CONSENT="));
+    }
+    // rotated user agents
+    assert_ne!(
+        header(&requests[0], "user-agent"),
+        header(&requests[1], "user-agent")
+    );
+    // the cookie of the first response is sent by the clone
+    assert!(!header(&requests[0], "cookie").unwrap().contains("NID=1"));
+    assert!(header(&requests[1], "cookie").unwrap().contains("NID=1"));
+    assert_eq!(
+        cookie_jar.get("translate.google.com", "NID").as_deref(),
+        Some("1")
+    );
+}
+
+#[test]
+fn test_user_agents_per_translator() {
+    let transport = FakeTransport::responding(translated("Bonjour"));
+    let agents = vec!["agent-a".to_string(), "agent-b".to_string()];
+    let first = GoogleTranslator::builder()
+        .user_agents(agents.clone())
+        .transport(transport.clone())
+        .build();
+    let second = GoogleTranslator::builder()
+        .user_agents(agents)
+        .transport(transport.clone())
+        .build();
+
+    first.translate_sync("Hello", "en", "fr").unwrap();
+    second.translate_sync("Hello", "en", "fr").unwrap();
+    first.clone().translate_sync("Hello", "en", "fr").unwrap();
+
+    // each translator has its own rotation, shared by its clones
+    let requests = transport.requests();
+    let agents: Vec<_> = requests
+        .iter()
+        .map(|request| header(request, "user-agent").unwrap())
+        .collect();
+    assert_eq!(agents, ["agent-a", "agent-a", "agent-b"]);
+}
+
+#[test]
+fn test_local_addresses_quarantine() {
+    let captcha = Response::new(302, "https://www.google.com/sorry/index", "");
+    let transport = FakeTransport::responding(captcha);
+    let addresses: Vec<IpAddr> = vec!["192.0.2.1".parse().unwrap(), "192.0.2.2".parse().unwrap()];
+    let translator = GoogleTranslator::builder()
+        .local_addresses(addresses.clone())
+        .transport(transport.clone())
+        .build();
+
+    for _ in 0..3 {
+        translator.translate_sync("Hello", "en", "fr").unwrap_err();
+    }
+
+    let requests = transport.requests();
+    let used: Vec<_> = requests.iter().map(|r| r.local_address.unwrap()).collect();
+    // both addresses got a captcha, the first one leaves the quarantine first
+    assert_eq!(used, [addresses[0], addresses[1], addresses[0]]);
+    assert_eq!(requests[0].effective_ip_version(), Some(IpVersion::V4));
+}
+
+#[test]
+fn test_ip_version() {
+    let transport = FakeTransport::responding(translated("Bonjour"));
+    let translator = GoogleTranslator::builder()
+        .ip_version(IpVersion::V6)
+        .transport(transport.clone())
+        .build();
+
+    translator.translate_sync("Hello", "en", "fr").unwrap();
+    let requests = transport.requests();
+    assert_eq!(requests[0].local_address, None);
+    assert_eq!(requests[0].effective_ip_version(), Some(IpVersion::V6));
+}
+
+/// Answers with a captcha on `translate.google.de`, with a translation elsewhere.
+#[derive(Debug, Default)]
+struct BlockedDomainTransport {
+    urls: Mutex<Vec<String>>,
+}
+
+impl Transport for BlockedDomainTransport {
+    fn send_sync(&self, request: &Request) -> Result<Response, Error> {
+        self.urls.lock().unwrap().push(request.url.clone());
+        if request.url.starts_with("https://translate.google.de/") {
+            Ok(Response::new(302, "https://www.google.de/sorry/index", ""))
+        } else {
+            Ok(translated("Bonjour"))
+        }
+    }
+
+    #[cfg(feature = "async")]
+    fn send_async<'a>(&'a self, request: &'a Request) -> BoxFuture<'a, Result<Response, Error>> {
+        Box::pin(async move { self.send_sync(request) })
+    }
+}
+
+#[test]
+fn test_domains_failover() {
+    let transport = Arc::new(BlockedDomainTransport::default());
+    let translator = GoogleTranslator::builder()
+        .domains(vec![
+            "translate.google.de".to_string(),
+            "translate.google.co.jp".to_string(),
+        ])
+        .transport(transport.clone())
+        .build();
+
+    let res = translator.translate_sync("Hello", "en", "fr").unwrap();
+    assert_eq!(res, "Bonjour");
+    // the quarantined domain is skipped by the next request
+    translator.translate_sync("Hello", "en", "fr").unwrap();
+
+    let urls = transport.urls.lock().unwrap();
+    assert_eq!(urls.len(), 3);
+    assert!(urls[0].starts_with("https://translate.google.de/m?tl=fr&sl=en&q=Hello"));
+    assert!(urls[1].starts_with("https://translate.google.co.jp/m?"));
+    assert!(urls[2].starts_with("https://translate.google.co.jp/m?"));
+}
+
+#[test]
+fn test_domains_all_blocked() {
+    let captcha = Response::new(302, "https://www.google.com/sorry/index", "");
+    let transport = FakeTransport::responding(captcha);
+    let translator = GoogleTranslator::builder()
+        .domains(vec![
+            "translate.google.de".to_string(),
+            "translate.google.fr".to_string(),
+        ])
+        .transport(transport.clone())
+        .build();
+
+    let err = translator.translate_sync("Hello", "en", "fr").unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::Captcha);
+    // each domain is tried once
+    assert_eq!(transport.requests().len(), 2);
+}
+
+#[test]
+fn test_api_domain() {
+    let json = r#"[[["Bonjour","Hello",null,null,10]],null,"en"]"#;
+    let transport = FakeTransport::responding(Response::new(
+        200,
+        "https://translate.googleapis.com/translate_a/single",
+        json,
+    ));
+    let translator = GoogleTranslator::builder()
+        .domain("translate.googleapis.com")
+        .transport(transport.clone())
+        .build();
+
+    let res = translator.translate_sync("Hello", "", "fr").unwrap();
+    assert_eq!(res, "Bonjour");
+    let requests = transport.requests();
+    assert_eq!(
+        requests[0].url,
+        "https://translate.googleapis.com/translate_a/single?client=gtx&sl=auto&tl=fr&dt=t&q=Hello"
+    );
+}
+
+#[test]
+fn test_custom_domain_url() {
+    let transport = FakeTransport::responding(translated("Bonjour"));
+    let translator = GoogleTranslator::builder()
+        .domain("http://127.0.0.1:8080/")
+        .transport(transport.clone())
+        .build();
+
+    translator.translate_sync("Hello", "en", "fr").unwrap();
+    let requests = transport.requests();
+    assert_eq!(
+        requests[0].url,
+        "http://127.0.0.1:8080/m?tl=fr&sl=en&q=Hello"
+    );
+}
+
+/// Answers with a captcha until `blocked` is unset, the async requests never finish
+/// while `hanging` is set. Fails without a request while `local_error` is set.
+#[derive(Debug, Default)]
+struct BanTransport {
+    blocked: AtomicBool,
+    local_error: AtomicBool,
+    #[cfg(feature = "async")]
+    hanging: AtomicBool,
+    requests: AtomicUsize,
+}
+
+impl Transport for BanTransport {
+    fn send_sync(&self, _: &Request) -> Result<Response, Error> {
+        if self.local_error.load(Ordering::SeqCst) {
+            return Err(Error::new(ErrorKind::Builder, "no client"));
+        }
+        self.requests.fetch_add(1, Ordering::SeqCst);
+        if self.blocked.load(Ordering::SeqCst) {
+            Ok(Response::new(302, "https://www.google.com/sorry/index", ""))
+        } else {
+            Ok(translated("Bonjour"))
+        }
+    }
+
+    #[cfg(feature = "async")]
+    fn send_async<'a>(&'a self, request: &'a Request) -> BoxFuture<'a, Result<Response, Error>> {
+        Box::pin(async move {
+            if self.hanging.load(Ordering::SeqCst) {
+                futures::future::pending::<()>().await;
+            }
+            self.send_sync(request)
+        })
+    }
+}
+
+#[test]
+fn test_circuit_breaker() {
+    let transport = Arc::new(BanTransport {
+        blocked: AtomicBool::new(true),
+        ..BanTransport::default()
+    });
+    let translator = GoogleTranslator::builder()
+        .circuit_breaker(CircuitBreaker::new(2, Duration::from_millis(50)))
+        .transport(transport.clone())
+        .build();
+    assert_eq!(translator.circuit_state(), Some(CircuitState::Closed));
+
+    for _ in 0..2 {
+        let err = translator.translate_sync("Hello", "en", "fr").unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::Captcha);
+    }
+    assert_eq!(translator.circuit_state(), Some(CircuitState::Open));
+
+    // fails fast without a request, for the clones too
+    let err = translator
+        .clone()
+        .translate_sync("Hello", "en", "fr")
+        .unwrap_err();
+    assert!(matches!(
+        err.kind(),
+        ErrorKind::CircuitOpen {
+            retry_after: Some(_)
+        }
+    ));
+    assert_eq!(err.backend(), Some("google"));
+    assert_eq!(transport.requests.load(Ordering::SeqCst), 2);
+
+    // the probe after the cooldown closes the circuit
+    transport.blocked.store(false, Ordering::SeqCst);
+    thread::sleep(Duration::from_millis(60));
+    assert_eq!(translator.circuit_state(), Some(CircuitState::HalfOpen));
+    let res = translator.translate_sync("Hello", "en", "fr").unwrap();
+    assert_eq!(res, "Bonjour");
+    assert_eq!(translator.circuit_state(), Some(CircuitState::Closed));
+    assert_eq!(transport.requests.load(Ordering::SeqCst), 3);
+}
+
+#[test]
+fn test_circuit_breaker_local_error() {
+    let transport = Arc::new(BanTransport {
+        blocked: AtomicBool::new(true),
+        ..BanTransport::default()
+    });
+    let translator = GoogleTranslator::builder()
+        .circuit_breaker(CircuitBreaker::new(1, Duration::from_millis(20)))
+        .transport(transport.clone())
+        .build();
+    translator.translate_sync("Hello", "en", "fr").unwrap_err();
+    thread::sleep(Duration::from_millis(30));
+
+    // the probe fails before reaching Google, the circuit isn't closed
+    transport.local_error.store(true, Ordering::SeqCst);
+    let err = translator.translate_sync("Hello", "en", "fr").unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::Builder);
+    assert_eq!(translator.circuit_state(), Some(CircuitState::HalfOpen));
+
+    // the next request probes again, Google still blocks it
+    transport.local_error.store(false, Ordering::SeqCst);
+    let err = translator.translate_sync("Hello", "en", "fr").unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::Captcha);
+    assert_eq!(translator.circuit_state(), Some(CircuitState::Open));
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn test_circuit_breaker_cancelled_probe() {
+    let transport = Arc::new(BanTransport {
+        blocked: AtomicBool::new(true),
+        ..BanTransport::default()
+    });
+    let translator = GoogleTranslator::builder()
+        .circuit_breaker(CircuitBreaker::new(1, Duration::from_millis(20)))
+        .total_timeout(Duration::from_millis(20))
+        .transport(transport.clone())
+        .build();
+    translator.translate_sync("Hello", "en", "fr").unwrap_err();
+    thread::sleep(Duration::from_millis(30));
+
+    // the probe is cut by the total timeout
+    transport.blocked.store(false, Ordering::SeqCst);
+    transport.hanging.store(true, Ordering::SeqCst);
+    let err =
+        futures::executor::block_on(translator.translate_async("Hello", "en", "fr")).unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::Timeout);
+    assert_eq!(translator.circuit_state(), Some(CircuitState::HalfOpen));
+
+    // the next request probes again
+    transport.hanging.store(false, Ordering::SeqCst);
+    let res = futures::executor::block_on(translator.translate_async("Hello", "en", "fr"));
+    assert_eq!(res.unwrap(), "Bonjour");
+    assert_eq!(translator.circuit_state(), Some(CircuitState::Closed));
+}
+
+#[test]
+fn test_circuit_breaker_connect_failed() {
+    let translator = GoogleTranslator::builder()
+        .proxy_address("http://127.0.0.1:1")
+        .circuit_breaker(CircuitBreaker::new(1, Duration::from_secs(60)))
+        .build();
+
+    let err = translator.translate_sync("Hello", "en", "fr").unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::ConnectFailed);
+    let err = translator.fetch_languages_sync("en").unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::CircuitOpen { .. }));
+
+    translator.circuit_breaker.as_ref().unwrap().reset();
+    assert_eq!(translator.circuit_state(), Some(CircuitState::Closed));
+    assert_eq!(GoogleTranslator::default().circuit_state(), None);
+
+    let config = BackendConfig::new().set("circuit_cooldown", "300");
+    let translator = GoogleTranslator::from_config(&config).unwrap();
+    let breaker = translator.circuit_breaker.unwrap();
+    assert_eq!(breaker.failure_threshold(), 5);
+    assert_eq!(breaker.cooldown(), Duration::from_secs(300));
+}
+
+/// Answers after `delay`, keeps the highest number of requests at once.
+#[derive(Debug, Default)]
+struct SlowTransport {
+    delay: Duration,
+    in_flight: AtomicUsize,
+    max_in_flight: AtomicUsize,
+}
+
+impl Transport for SlowTransport {
+    fn send_sync(&self, _: &Request) -> Result<Response, Error> {
+        let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        self.max_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+        thread::sleep(self.delay);
+        self.in_flight.fetch_sub(1, Ordering::SeqCst);
+        Ok(translated("Bonjour"))
+    }
+
+    #[cfg(feature = "async")]
+    fn send_async<'a>(&'a self, request: &'a Request) -> BoxFuture<'a, Result<Response, Error>> {
+        Box::pin(async move { self.send_sync(request) })
+    }
+}
+
+#[test]
+fn test_adaptive_concurrency() {
+    let transport = Arc::new(BanTransport::default());
+    let concurrency = AdaptiveConcurrency::new(1, 4);
+    let translator = GoogleTranslator::builder()
+        .adaptive_concurrency(concurrency.clone())
+        .transport(transport.clone())
+        .build();
+
+    for _ in 0..10 {
+        translator.translate_sync("Hello", "en", "fr").unwrap();
+    }
+    assert_eq!(concurrency.limit(), 4);
+
+    transport.blocked.store(true, Ordering::SeqCst);
+    let err = translator.translate_sync("Hello", "en", "fr").unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::Captcha);
+    assert_eq!(concurrency.limit(), 2);
+    assert_eq!(concurrency.in_flight(), 0);
+}
+
+#[test]
+fn test_adaptive_concurrency_limit() {
+    let transport = Arc::new(SlowTransport {
+        delay: Duration::from_millis(5),
+        ..SlowTransport::default()
+    });
+    // too slow, the limit stays at the minimum
+    let translator = GoogleTranslator::builder()
+        .text_limit(5usize)
+        .adaptive_concurrency(
+            AdaptiveConcurrency::new(1, 4).with_latency_threshold(Duration::from_millis(1)),
+        )
+        .transport(transport.clone())
+        .build();
+
+    // translated on 4 threads, one request at a time
+    let res = translator
+        .translate_sync("One. Two. Six. Ten.", "en", "fr")
+        .unwrap();
+    assert_eq!(res, "BonjourBonjourBonjourBonjour");
+    assert_eq!(transport.max_in_flight.load(Ordering::SeqCst), 1);
+
+    let config = BackendConfig::new()
+        .set("adaptive_concurrency", "2, 32")
+        .set("latency_threshold", "1.5");
+    let translator = GoogleTranslator::from_config(&config).unwrap();
+    let concurrency = translator.adaptive_concurrency.unwrap();
+    assert_eq!((concurrency.min(), concurrency.max()), (2, 32));
+    assert_eq!(
+        concurrency.latency_threshold(),
+        Some(Duration::from_millis(1500))
+    );
+    let config = BackendConfig::new().set("adaptive_concurrency", "8");
+    let err = GoogleTranslator::from_config(&config).unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::Builder);
+}
+
+/// Never answers the requests without proxy, answers the other ones at once.
+#[cfg(feature = "async")]
+#[derive(Debug, Default)]
+struct HangingTransport {
+    proxies: Mutex<Vec<Option<String>>>,
+}
+
+#[cfg(feature = "async")]
+impl Transport for HangingTransport {
+    fn send_sync(&self, request: &Request) -> Result<Response, Error> {
+        self.proxies
+            .lock()
+            .unwrap()
+            .push(request.proxy_address.clone());
+        Ok(translated("Bonjour"))
+    }
+
+    fn send_async<'a>(&'a self, request: &'a Request) -> BoxFuture<'a, Result<Response, Error>> {
+        if request.proxy_address.is_none() {
+            self.proxies.lock().unwrap().push(None);
+            return Box::pin(futures::future::pending());
+        }
+        Box::pin(async move { self.send_sync(request) })
+    }
+}
+
+#[cfg(feature = "async")]
+#[tokio::test]
+async fn test_hedging() {
+    let transport = Arc::new(HangingTransport::default());
+    let hedging = Hedging::new(Duration::from_millis(20))
+        .with_budget(1.0)
+        .with_proxy_address("socks5://127.0.0.1:9050");
+    let translator = GoogleTranslator::builder()
+        .text_limit(5usize)
+        .hedging(hedging.clone())
+        .transport(transport.clone())
+        .build();
+
+    let res = translator
+        .translate_async("One. Two.", "en", "fr")
+        .await
+        .unwrap();
+    assert_eq!(res, "BonjourBonjour");
+    assert_eq!(hedging.hedged(), 2);
+    let proxies = transport.proxies.lock().unwrap();
+    assert_eq!(proxies.len(), 4);
+    assert_eq!(proxies.iter().filter(|proxy| proxy.is_some()).count(), 2);
+    drop(proxies);
+
+    // never for the sync requests
+    translator.translate_sync("Hello", "en", "fr").unwrap();
+    assert_eq!(hedging.hedged(), 2);
+
+    let config = BackendConfig::new()
+        .set("hedging_delay", "0.5")
+        .set("hedging_percentile", "0.99")
+        .set("hedging_proxy_address", "http://127.0.0.1:8080");
+    let translator = GoogleTranslator::from_config(&config).unwrap();
+    let hedging = translator.hedging.unwrap();
+    assert_eq!(hedging.delay(), Duration::from_millis(500));
+    assert_eq!(hedging.proxy_address(), Some("http://127.0.0.1:8080"));
+}
+
+#[cfg(feature = "async")]
+#[tokio::test]
+async fn test_hedging_permit() {
+    // the duplicate waits for a permit of its own, never free here
+    let transport = Arc::new(HangingTransport::default());
+    let concurrency = AdaptiveConcurrency::new(1, 1);
+    let translator = GoogleTranslator::builder()
+        .adaptive_concurrency(concurrency.clone())
+        .hedging(Hedging::new(Duration::from_millis(10)).with_budget(1.0))
+        .transport(transport.clone())
+        .build();
+    let (timer, elapsed) = futures::channel::oneshot::channel();
+    thread::spawn(move || {
+        thread::sleep(Duration::from_millis(100));
+        timer.send(()).unwrap();
+    });
+    let translation = Box::pin(translator.translate_async("Hello", "en", "fr"));
+    let res = futures::future::select(translation, elapsed).await;
+    assert!(matches!(res, futures::future::Either::Right(_)));
+    drop(res);
+    assert_eq!(*transport.proxies.lock().unwrap(), vec![None]);
+    assert_eq!(concurrency.in_flight(), 0);
+
+    let concurrency = AdaptiveConcurrency::new(2, 2);
+    let translator = GoogleTranslator {
+        adaptive_concurrency: Some(concurrency.clone()),
+        hedging: Some(
+            Hedging::new(Duration::from_millis(10))
+                .with_budget(1.0)
+                .with_proxy_address("socks5://127.0.0.1:9050"),
+        ),
+        ..translator
+    };
+    let res = translator
+        .translate_async("Hello", "en", "fr")
+        .await
+        .unwrap();
+    assert_eq!(res, "Bonjour");
+    assert_eq!(concurrency.in_flight(), 0);
+}
+
+#[test]
+fn test_refused_connection() {
+    let translator = GoogleTranslator::builder()
+        .proxy_address("http://127.0.0.1:1")
+        .build();
+
+    let err = translator
+        .translate_sync("Hello, world!", "en", "fr")
+        .unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::ConnectFailed);
+    assert!(err.is_retryable());
+}
+
+#[test]
+fn test_invalid_proxy() {
+    let translator = GoogleTranslator::builder()
+        .proxy_address("ftp://127.0.0.1:1")
+        .build();
+
+    let err = translator
+        .translate_sync("Hello, world!", "en", "fr")
+        .unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::Builder);
+    assert!(!err.is_retryable());
+}
+
+#[test]
+fn test_tls_config() {
+    let tls = TlsConfig::new()
+        .add_root_certificate_pem(include_bytes!("certs/ca.pem").to_vec())
+        .identity_pem(include_bytes!("certs/client.pem").to_vec());
+    let translator = GoogleTranslator::builder()
+        .proxy_address("http://127.0.0.1:1")
+        .tls(tls)
+        .build();
+
+    // the certificates are valid, the request goes as far as the proxy
+    let err = translator.translate_sync("Hello", "en", "fr").unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::ConnectFailed);
+}
+
+#[test]
+fn test_invalid_identity() {
+    let tls = TlsConfig::new().identity_pem(include_bytes!("certs/ca.pem").to_vec());
+    let translator = GoogleTranslator::builder()
+        .proxy_address("http://127.0.0.1:1")
+        .tls(tls)
+        .build();
+
+    let err = translator.translate_sync("Hello", "en", "fr").unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::Builder);
+}
+
+#[cfg(feature = "ureq")]
+#[test]
+fn test_ureq_transport() {
+    let translator = GoogleTranslator::builder()
+        .proxy_address("http://127.0.0.1:1")
+        .transport(Arc::new(translators::UreqTransport::new()))
+        .build();
+
+    let err = translator.translate_sync("Hello", "en", "fr").unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::ConnectFailed);
+
+    let translator = GoogleTranslator {
+        local_addresses: Some(vec!["127.0.0.1".parse().unwrap()].into()),
+        ..translator
+    };
+    let err = translator.translate_sync("Hello", "en", "fr").unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::Builder);
+}
+
+#[cfg(feature = "reqwest")]
+#[test]
+fn test_reqwest_local_address() {
+    let translator = GoogleTranslator::builder()
+        .proxy_address("http://127.0.0.1:1")
+        .local_address("127.0.0.1".parse().unwrap())
+        .transport(Arc::new(translators::ReqwestTransport::new()))
+        .build();
+
+    let err = translator.translate_sync("Hello", "en", "fr").unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::ConnectFailed);
+}
+
+#[cfg(feature = "reqwest")]
+#[test]
+fn test_reqwest_client() {
+    // the proxy of the translator isn't used by a preconfigured client
+    let client = reqwest::blocking::Client::builder()
+        .proxy(reqwest::Proxy::all("http://127.0.0.1:1").unwrap())
+        .build()
+        .unwrap();
+    let translator = GoogleTranslator::builder()
+        .reqwest_blocking_client(client)
+        .build();
+
+    let err = translator.translate_sync("Hello", "en", "fr").unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::ConnectFailed);
+}
+
+#[cfg(feature = "reqwest")]
+#[test]
+fn test_reqwest_client_settings() {
+    // the settings of the translator can't be applied to a preconfigured client
+    let translators = [
+        GoogleTranslator::builder().proxy_address("http://127.0.0.1:1"),
+        GoogleTranslator::builder().local_address("127.0.0.1".parse().unwrap()),
+        GoogleTranslator::builder().tls(TlsConfig::new()),
+        GoogleTranslator::builder().ip_version(IpVersion::V4),
+    ];
+    for (translator, setting) in
+        translators
+            .into_iter()
+            .zip(["proxy_address", "local_addresses", "tls", "ip_version"])
+    {
+        let translator = translator
+            .reqwest_blocking_client(reqwest::blocking::Client::new())
+            .build();
+        let err = translator.translate_sync("Hello", "en", "fr").unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::Builder, "{setting}");
+        assert!(err.to_string().contains(setting), "{err}");
+    }
+}
@@ -0,0 +1,148 @@
+//! Yandex against a fake API.
+
+mod common;
+
+use common::FakeTransport;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use translators::{
+    BackendConfig, ErrorKind, Method, Request, Response, Translator, YandexTranslator,
+};
+
+/// Answers like the Yandex API, the translations prefix the texts with the language pair.
+fn yandex(request: &Request) -> Response {
+    let form = form(request);
+    let answer = json!({
+        "code": 200,
+        "lang": form["lang"],
+        "text": [format!("[{}]{}", form["lang"].as_str().unwrap(), form["text"].as_str().unwrap())],
+    });
+    Response::new(200, &request.url, answer.to_string())
+}
+
+/// Form of the request as a JSON object.
+fn form(request: &Request) -> Value {
+    let fields = request.body.as_deref().unwrap().split('&').map(|pair| {
+        let (name, value) = pair.split_once('=').unwrap();
+        (
+            name.to_string(),
+            Value::from(urlencoding::decode(value).unwrap().into_owned()),
+        )
+    });
+    Value::Object(fields.collect())
+}
+
+fn translator(transport: Arc<FakeTransport>) -> YandexTranslator {
+    YandexTranslator::builder()
+        .url("http://yandex.local/")
+        .ucid("0123456789abcdef0123456789abcdef")
+        .transport(transport)
+        .build()
+}
+
+#[test]
+fn test_translate() {
+    let transport = FakeTransport::new(yandex);
+    let translator = translator(transport.clone());
+
+    let res = translator.translate_sync("Hello", "en", "fr").unwrap();
+    assert_eq!(res, "[en-fr]Hello");
+    // only the target for the detection
+    let res = translator.translate_sync("Hello", "", "fr").unwrap();
+    assert_eq!(res, "[fr]Hello");
+
+    let requests = transport.requests();
+    assert_eq!(requests[0].method, Method::Post);
+    assert_eq!(
+        requests[0].url,
+        "http://yandex.local/api/v1/tr.json/translate?ucid=0123456789abcdef0123456789abcdef&srv=android&format=text"
+    );
+}
+
+#[test]
+fn test_translate_chunks() {
+    let transport = FakeTransport::new(yandex);
+    let translator = YandexTranslator {
+        text_limit: 6,
+        ..translator(transport.clone())
+    };
+
+    let res = translator
+        .translate_sync("Hello world", "en", "de")
+        .unwrap();
+    assert_eq!(res, "[en-de]Hello [en-de]world");
+    assert_eq!(transport.requests().len(), 2);
+}
+
+#[test]
+fn test_random_ucid() {
+    let first = YandexTranslator::default().ucid;
+    let second = YandexTranslator::default().ucid;
+    assert_eq!(first.len(), 32);
+    assert!(first.chars().all(|c| c.is_ascii_hexdigit()));
+    assert_ne!(first, second);
+}
+
+#[test]
+fn test_errors() {
+    let transport = FakeTransport::failing(
+        200,
+        r#"{"code": 404, "message": "Maximum daily translated text volume exceeded"}"#,
+    );
+    let err = translator(transport)
+        .translate_sync("Hello", "en", "fr")
+        .unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::QuotaExceeded);
+    assert_eq!(
+        err.message(),
+        "Maximum daily translated text volume exceeded"
+    );
+    assert_eq!(err.status(), Some(404));
+    assert_eq!(err.backend(), Some("yandex"));
+    assert!(!err.is_retryable());
+
+    let transport = FakeTransport::failing(
+        501,
+        r#"{"code": 501, "message": "The specified translation direction is not supported"}"#,
+    );
+    let err = translator(transport)
+        .translate_sync("Hello", "en", "xx")
+        .unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::UnsupportedLanguage);
+    assert_eq!(err.chunk_index(), Some(0));
+
+    let transport = FakeTransport::failing(429, "");
+    let err = translator(transport)
+        .translate_sync("Hello", "en", "fr")
+        .unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::RateLimited { .. }));
+
+    let transport = FakeTransport::failing(302, "");
+    let err = YandexTranslator {
+        url: "http://yandex.local/showcaptcha?retpath=".to_string(),
+        ..translator(transport)
+    }
+    .translate_sync("Hello", "en", "fr")
+    .unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::Captcha);
+}
+
+#[test]
+fn test_from_config() {
+    let config = BackendConfig::new()
+        .set("ucid", "fedcba9876543210fedcba9876543210")
+        .set("text_limit", "1000");
+    let translator = YandexTranslator::from_config(&config).unwrap();
+    assert_eq!(translator.ucid, "fedcba9876543210fedcba9876543210");
+    assert_eq!(translator.text_limit, 1000);
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn test_translate_async() {
+    let transport = FakeTransport::new(yandex);
+    let translator = translator(transport);
+
+    let res = futures::executor::block_on(translator.translate_async("Hello", "", "de")).unwrap();
+    assert_eq!(res, "[de]Hello");
+}